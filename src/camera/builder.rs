@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::environment::constant::ConstantEnvironment;
+use crate::environment::environment::Environment;
+use crate::environment::gradient::GradientEnvironment;
+
+use crate::geometry::vec3::{Color, Point3, Vec3};
+
+use crate::hittables::hittable_list::HittableList;
+
+use super::camera::Camera;
+use super::error::CameraError;
+
+pub struct CameraBuilder {
+    pub(super) aspect_ratio: f64,
+    pub(super) image_width: u32,
+    pub(super) samples_per_pixel: u32,
+    pub(super) max_depth: u32,
+    pub(super) vfov: f64,
+    pub(super) look_from: Point3,
+    pub(super) look_at: Point3,
+    pub(super) vup: Vec3,
+    pub(super) defocus_angle: f64,
+    pub(super) focus_dist: Option<f64>,
+    pub(super) shutter_open: f64,
+    pub(super) shutter_close: f64,
+    pub(super) threads: usize,
+    pub(super) seed: u64,
+    pub(super) environment: Arc<dyn Environment>,
+    pub(super) fog_density: f64,
+    pub(super) fog_albedo: Color,
+    pub(super) lights: Arc<HittableList>,
+}
+
+impl Default for CameraBuilder {
+    fn default() -> Self {
+        Self {
+            aspect_ratio: 1_f64,
+            image_width: 100,
+            samples_per_pixel: 10,
+            max_depth: 10,
+            vfov: 90_f64,
+            look_from: Point3::new(0_f64, 0_f64, 0_f64),
+            look_at: Point3::new(0_f64, 0_f64, -1_f64),
+            vup: Vec3::new(0_f64, 1_f64, 0_f64),
+            defocus_angle: 0_f64,
+            focus_dist: None,
+            shutter_open: 0_f64,
+            shutter_close: 0_f64,
+            threads: 0,
+            seed: rand::thread_rng().gen(),
+            environment: Arc::new(GradientEnvironment::sky()),
+            fog_density: 0_f64,
+            fog_albedo: Color::new(1_f64, 1_f64, 1_f64),
+            lights: Arc::new(HittableList::new()),
+        }
+    }
+}
+
+impl CameraBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Ratio of image width over height.
+    pub fn aspect_ratio(mut self, aspect_ratio: f64) -> Self {
+        self.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    // Rendered image width in pixel count.
+    pub fn image_width(mut self, image_width: u32) -> Self {
+        self.image_width = image_width;
+        self
+    }
+
+    // Count of random samples for each pixel.
+    pub fn samples_per_pixel(mut self, samples_per_pixel: u32) -> Self {
+        self.samples_per_pixel = samples_per_pixel;
+        self
+    }
+
+    // Maximum number of ray bounces into the scene.
+    pub fn max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    // Vertical view angle (field of view) in degrees.
+    pub fn vfov(mut self, vfov: f64) -> Self {
+        self.vfov = vfov;
+        self
+    }
+
+    // Point the camera is looking from.
+    pub fn look_from(mut self, look_from: Point3) -> Self {
+        self.look_from = look_from;
+        self
+    }
+
+    // Point the camera is looking at.
+    pub fn look_at(mut self, look_at: Point3) -> Self {
+        self.look_at = look_at;
+        self
+    }
+
+    // Camera-relative "up" direction.
+    pub fn vup(mut self, vup: Vec3) -> Self {
+        self.vup = vup;
+        self
+    }
+
+    // Variation angle of rays through each pixel, in degrees. Zero disables depth of field.
+    pub fn defocus_angle(mut self, defocus_angle: f64) -> Self {
+        self.defocus_angle = defocus_angle;
+        self
+    }
+
+    // Distance from `look_from` to the plane of perfect focus. Defaults to the distance
+    // between `look_from` and `look_at`.
+    pub fn focus_dist(mut self, focus_dist: f64) -> Self {
+        self.focus_dist = Some(focus_dist);
+        self
+    }
+
+    // Interval over which the shutter is open. Each ray is given a random time within it,
+    // so objects moving during the interval are blurred. Object motion runs from time 0 to
+    // time 1. Defaults to an instantaneous shutter at time 0.
+    pub fn shutter(mut self, open: f64, close: f64) -> Self {
+        self.shutter_open = open;
+        self.shutter_close = close;
+        self
+    }
+
+    // Number of render worker threads. Zero uses all available cores.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    // Seed for the per-pixel random streams. Renders with the same seed and settings produce
+    // identical images regardless of the thread count. Defaults to a random seed.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // Light surrounding the scene, seen by rays that leave it. Defaults to a sky gradient.
+    pub fn environment(mut self, environment: Arc<dyn Environment>) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    // Surrounds the scene with a constant color. Black turns off all light except that of
+    // emissive materials.
+    pub fn background(self, background: Color) -> Self {
+        self.environment(Arc::new(ConstantEnvironment::new(background)))
+    }
+
+    // Fills the space between surfaces with a homogeneous medium that scatters light
+    // isotropically, `density` times per unit distance on average, keeping `albedo` of it at
+    // each scattering. Rays that leave the scene reach the environment unscattered, as if the
+    // fog ended at its edge. Zero density turns fog off, which is the default.
+    pub fn fog(mut self, density: f64, albedo: Color) -> Self {
+        self.fog_density = density;
+        self.fog_albedo = albedo;
+        self
+    }
+
+    // Light sources sampled directly at every bounce, which greatly reduces the noise of
+    // small lights. Each must also be part of the rendered world, and be able to aim rays
+    // at itself (see `Hittable::pdf_value`). Lights left out are still found by chance.
+    // The environment is sampled too, unless it is black.
+    pub fn lights(mut self, lights: HittableList) -> Self {
+        self.lights = Arc::new(lights);
+        self
+    }
+
+    // Checks that `look_from`, `look_at` and `vup` define a view, as `build` requires.
+    pub fn validate(&self) -> Result<(), CameraError> {
+        let view: Vec3 = self.look_from - self.look_at;
+        if view.near_zero() {
+            return Err(CameraError::NoViewDirection);
+        }
+        if Vec3::cross(&self.vup, &Vec3::unit_vector(view)).near_zero() {
+            return Err(CameraError::UpParallelToView);
+        }
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<Camera, CameraError> {
+        self.validate()?;
+        Ok(Camera::init(&self))
+    }
+}
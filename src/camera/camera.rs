@@ -1,16 +1,43 @@
-use image::{ImageBuffer, Rgb};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
 use indicatif::ProgressBar;
 
+use crate::environment::environment::Environment;
+
 use crate::geometry::ray::Ray;
 use crate::geometry::vec3::{Color, Point3, Vec3};
 use crate::geometry::interval::Interval;
 
 use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::hittable_list::HittableList;
+
+use crate::materials::isotropic::Isotropic;
+use crate::materials::material::Material;
+
+use crate::output::framebuffer::Framebuffer;
+
+use crate::sampling::pdf::{power_heuristic, EnvironmentPdf, HittablePdf, MixturePdf, Pdf};
+use crate::sampling::sampler::Sampler;
 
-use crate::random_double;
+use crate::degrees_to_radians;
+
+use super::builder::CameraBuilder;
+
+// Edge length, in pixels, of the square tiles handed out to render workers.
+const TILE_SIZE: u32 = 32;
+
+// A rectangular block of pixels, `[x0, x1) x [y0, y1)`.
+struct Tile {
+    x0: u32,
+    y0: u32,
+    x1: u32,
+    y1: u32,
+}
 
 pub struct Camera {
-    #[allow(dead_code)] // 'aspect_ratio' unused
     aspect_ratio: f64,
     image_width: u32,
     image_height: u32,
@@ -20,39 +47,129 @@ pub struct Camera {
     pixel_delta_v: Vec3,
     samples_per_pixel: u32,
     pixel_samples_scale: f64,
+    max_depth: u32,
+    defocus_angle: f64,
+    defocus_disk_u: Vec3,
+    defocus_disk_v: Vec3,
+    shutter_open: f64,
+    shutter_close: f64,
+    threads: usize,
+    seed: u64,
+    environment: Arc<dyn Environment>,
+    fog_density: f64,
+    fog_phase: Arc<dyn Material>,
+    lights: Arc<HittableList>,
+    // Chance that a direct light sample aims at the lights rather than the environment, or
+    // None when neither gives any light to sample.
+    light_fraction: Option<f64>,
 }
 
 impl Camera {
-    pub(crate) fn new(aspect_ratio: f64, image_width: u32, samples: u32) -> Self {
-        Self::init(aspect_ratio, image_width, samples)
+    pub fn builder() -> CameraBuilder {
+        CameraBuilder::new()
     }
 
-    pub(crate) fn render<T: Hittable>(&self, world: &T) -> ImageBuffer<Rgb<u16>, Vec<u16>> {
+    pub fn aspect_ratio(&self) -> f64 {
+        self.aspect_ratio
+    }
+
+    pub fn image_width(&self) -> u32 {
+        self.image_width
+    }
+
+    pub fn samples_per_pixel(&self) -> u32 {
+        self.samples_per_pixel
+    }
+
+    pub(crate) fn render<T: Hittable>(&self, world: &T) -> Framebuffer {
         // Render
+        let tiles: Vec<Tile> = self.tiles();
+        let next_tile = AtomicUsize::new(0);
         let bar = ProgressBar::new(self.image_width as u64 * self.image_height as u64);
-        let img = ImageBuffer::from_fn(self.image_width, self.image_height, |i, j| {
-    
-            let mut pixel_color = Color::default();
-            for _ in 0..self.samples_per_pixel {
-                let r: Ray = self.get_ray(i, j);
-                pixel_color += Self::ray_color(&r, world);
-            }
-            pixel_color = pixel_color * self.pixel_samples_scale;
 
-            let intensity = Interval::new(0_f64, 0.999);
-            let r: u16 = (u16::MAX as f64 * intensity.clamp(pixel_color.x())) as u16;
-            let g: u16 = (u16::MAX as f64 * intensity.clamp(pixel_color.y())) as u16;
-            let b: u16 = (u16::MAX as f64 * intensity.clamp(pixel_color.z())) as u16;
+        // Workers pull tiles off a shared counter until none are left, and hand back the
+        // finished pixels tagged with their tile index.
+        let rendered: Vec<(usize, Vec<Color>)> = thread::scope(|s| {
+            let workers: Vec<_> = (0..self.threads)
+                .map(|_| s.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next_tile.fetch_add(1, Ordering::Relaxed);
+                        let Some(tile) = tiles.get(index) else {
+                            break;
+                        };
+
+                        done.push((index, self.render_tile(tile, world, &bar)));
+                    }
+                    done
+                }))
+                .collect();
 
-            bar.inc(1);
-            image::Rgb([r, g, b])
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("render worker panicked"))
+                .collect()
         });
         bar.finish();
 
-        img
+        let mut framebuffer = Framebuffer::new(self.image_width, self.image_height);
+        for (index, pixels) in rendered {
+            let tile = &tiles[index];
+            let width = tile.x1 - tile.x0;
+            for (k, pixel) in pixels.into_iter().enumerate() {
+                let k = k as u32;
+                framebuffer.set(tile.x0 + k % width, tile.y0 + k / width, pixel);
+            }
+        }
+
+        framebuffer
+    }
+
+    fn tiles(&self) -> Vec<Tile> {
+        let mut tiles = Vec::new();
+        for y0 in (0..self.image_height).step_by(TILE_SIZE as usize) {
+            for x0 in (0..self.image_width).step_by(TILE_SIZE as usize) {
+                tiles.push(Tile {
+                    x0,
+                    y0,
+                    x1: (x0 + TILE_SIZE).min(self.image_width),
+                    y1: (y0 + TILE_SIZE).min(self.image_height),
+                });
+            }
+        }
+        tiles
+    }
+
+    fn render_tile<T: Hittable>(&self, tile: &Tile, world: &T, bar: &ProgressBar) -> Vec<Color> {
+        let mut pixels = Vec::with_capacity(((tile.x1 - tile.x0) * (tile.y1 - tile.y0)) as usize);
+        for j in tile.y0..tile.y1 {
+            for i in tile.x0..tile.x1 {
+                pixels.push(self.render_pixel(i, j, world));
+            }
+            bar.inc((tile.x1 - tile.x0) as u64);
+        }
+        pixels
     }
 
-    fn init(aspect_ratio: f64, image_width: u32, samples: u32) -> Self {
+    fn render_pixel<T: Hittable>(&self, i: u32, j: u32, world: &T) -> Color {
+        // Each pixel gets its own random stream derived from the camera seed, so the result
+        // does not depend on scheduling or tiling.
+        let mut rng: Sampler = Sampler::for_pixel(self.seed, i, j);
+
+        let mut pixel_color = Color::default();
+        for _ in 0..self.samples_per_pixel {
+            let r: Ray = self.get_ray(i, j, &mut rng);
+            pixel_color += self.ray_color(&r, world, &mut rng);
+        }
+        pixel_color *= self.pixel_samples_scale;
+
+        pixel_color
+    }
+
+    pub(super) fn init(builder: &CameraBuilder) -> Self {
+        let aspect_ratio: f64 = builder.aspect_ratio;
+        let image_width: u32 = builder.image_width;
+
         // Calculate the image height, and ensure that it's at least 1.
         let image_height: u32 = {
             let height: u32 = (image_width as f64 / aspect_ratio) as u32;
@@ -63,28 +180,58 @@ impl Camera {
             }
         };
 
-        let pixel_samples_scale: f64 = 1_f64 / samples as f64;
+        let pixel_samples_scale: f64 = 1_f64 / builder.samples_per_pixel as f64;
 
         // camera
+        let camera_center: Point3 = builder.look_from;
+
+        // Determine viewport dimensions.
         // Viewport widths less than one are ok since they are real valued.
-        let focal_length = 1_f64;
-        let viewport_height = 2_f64;
-        let viewport_width = viewport_height * (image_width as f64 / image_height as f64);
-        let camera_center = Point3::new(0_f64, 0_f64, 0_f64);
+        let focus_dist: f64 = builder.focus_dist
+            .unwrap_or_else(|| (builder.look_from - builder.look_at).length());
+        let theta: f64 = degrees_to_radians(builder.vfov);
+        let h: f64 = (theta / 2_f64).tan();
+        let viewport_height: f64 = 2_f64 * h * focus_dist;
+        let viewport_width: f64 = viewport_height * (image_width as f64 / image_height as f64);
+
+        // Calculate the u,v,w unit basis vectors for the camera coordinate frame.
+        let w: Vec3 = Vec3::unit_vector(builder.look_from - builder.look_at);
+        let u: Vec3 = Vec3::unit_vector(Vec3::cross(&builder.vup, &w));
+        let v: Vec3 = Vec3::cross(&w, &u);
 
         // Calculate the vectors across the horizontal and down the vertical viewport edges.
-        let viewport_u = Vec3::new(viewport_width, 0_f64, 0_f64);
-        let viewport_v = Vec3::new(0_f64, -1_f64 * viewport_height, 0_f64);
+        let viewport_u: Vec3 = viewport_width * u;
+        let viewport_v: Vec3 = viewport_height * -v;
 
         // Calculate the horizontal and vertical delta vectors from pixel to pixel.
         let pixel_delta_u = viewport_u / image_width as f64;
         let pixel_delta_v = viewport_v / image_height as f64;
 
         // Calculate the location of the upper left pixel.
-        let camera_to_viewport_vec = camera_center - Vec3::new(0_f64, 0_f64, focal_length);
+        let camera_to_viewport_vec = camera_center - (focus_dist * w);
         let viewport_upper_left = camera_to_viewport_vec - (0.5 * viewport_u) - (0.5 * viewport_v);
         let pixel_00_loc = viewport_upper_left + 0.5 * (pixel_delta_u + pixel_delta_v);
 
+        // Calculate the camera defocus disk basis vectors.
+        let defocus_radius: f64 = focus_dist * degrees_to_radians(builder.defocus_angle / 2_f64).tan();
+        let defocus_disk_u: Vec3 = u * defocus_radius;
+        let defocus_disk_v: Vec3 = v * defocus_radius;
+
+        // Direct light samples are shared evenly between the lights and the environment when
+        // both give light.
+        let light_fraction: Option<f64> = match (builder.lights.objects().is_empty(), builder.environment.is_black()) {
+            (true, true) => None,
+            (false, true) => Some(1_f64),
+            (true, false) => Some(0_f64),
+            (false, false) => Some(0.5),
+        };
+
+        let threads: usize = if builder.threads > 0 {
+            builder.threads
+        } else {
+            thread::available_parallelism().map_or(1, NonZeroUsize::get)
+        };
+
         Self {
             aspect_ratio,
             image_width,
@@ -93,37 +240,203 @@ impl Camera {
             pixel_00_loc,
             pixel_delta_u,
             pixel_delta_v,
-            samples_per_pixel: samples,
+            samples_per_pixel: builder.samples_per_pixel,
             pixel_samples_scale,
+            max_depth: builder.max_depth,
+            defocus_angle: builder.defocus_angle,
+            defocus_disk_u,
+            defocus_disk_v,
+            shutter_open: builder.shutter_open,
+            shutter_close: builder.shutter_close,
+            threads,
+            seed: builder.seed,
+            environment: Arc::clone(&builder.environment),
+            fog_density: builder.fog_density,
+            fog_phase: Arc::new(Isotropic::new(builder.fog_albedo)),
+            lights: Arc::clone(&builder.lights),
+            light_fraction,
         }
     }
 
-    fn get_ray(&self, i: u32, j: u32) -> Ray {
-        let offset: Vec3 = Self::sample_square();
+    fn get_ray(&self, i: u32, j: u32, rng: &mut Sampler) -> Ray {
+        // Construct a camera ray originating from the defocus disk and directed at a randomly
+        // sampled point around the pixel location i, j, at a random time while the shutter
+        // is open.
+
+        let offset: Vec3 = Self::sample_square(rng);
         let pixel_sample: Vec3 = self.pixel_00_loc 
             + ((i as f64 + offset.x()) * self.pixel_delta_u) 
             + ((j as f64 + offset.y()) * self.pixel_delta_v);
 
-        let ray_origin: Vec3 = self.center;
+        let ray_origin: Vec3 = if self.defocus_angle <= 0_f64 {
+            self.center
+        } else {
+            self.defocus_disk_sample(rng)
+        };
         let ray_direction: Vec3 = pixel_sample - ray_origin;
+        let ray_time: f64 = if self.shutter_close > self.shutter_open {
+            rng.random_double_range(self.shutter_open, self.shutter_close)
+        } else {
+            self.shutter_open
+        };
+
+        Ray::with_time(ray_origin, ray_direction, ray_time)
+    }
 
-        Ray::new(ray_origin, ray_direction)
+    fn sample_square(rng: &mut Sampler) -> Vec3 {
+        // Returns the vector to a random point in the [-.5,-.5]-[+.5,+.5] unit square.
+        Vec3::new(rng.random_double() - 0.5, rng.random_double() - 0.5, 0_f64)
     }
 
-    fn sample_square() -> Vec3 {
-        Vec3::new(random_double() - 0.5, random_double() - 0.5, 0_f64)
+    fn defocus_disk_sample(&self, rng: &mut Sampler) -> Point3 {
+        // Returns a random point in the camera defocus disk.
+        let p: Vec3 = Vec3::random_in_unit_disk(rng);
+        self.center + (p[0] * self.defocus_disk_u) + (p[1] * self.defocus_disk_v)
     }
 
-    fn ray_color<T: Hittable>(r: &Ray, world: &T) -> Color {
-        let mut rec: HitRecord = HitRecord::default();
-        if world.hit(&r, &Interval::new(0_f64, f64::INFINITY), &mut rec) {
-            let direction: Vec3 = Vec3::random_on_hemisphere(&rec.normal);
-            return 0.5 * Self::ray_color(&Ray::new(rec.p, direction), world)
+    fn ray_color<T: Hittable>(&self, r: &Ray, world: &T, rng: &mut Sampler) -> Color {
+        // Follow the path one bounce at a time, accumulating the product of the surface
+        // attenuations (the path throughput) instead of recursing on each scattered ray.
+        // Light emitted at each hit reaches the camera scaled by the throughput so far.
+        //
+        // At bounces off materials other than perfect mirrors, the lights are also sampled
+        // directly. Light the path then finds by itself is weighted against the chance of
+        // direct sampling finding it too, so that between them it is counted once.
+        let mut ray: Ray = *r;
+        let mut throughput: Color = Color::new(1_f64, 1_f64, 1_f64);
+        let mut radiance: Color = Color::default();
+
+        // Density with which the last bounce chose the direction of `ray`, when the lights
+        // were sampled directly there.
+        let mut scattering_pdf: Option<f64> = None;
+
+        for _ in 0..self.max_depth {
+            let mut rec: HitRecord = HitRecord::default();
+
+            // Ignore hits very close to the ray origin to avoid self-intersection ("shadow acne").
+            if !world.hit(&ray, &Interval::new(0.001, f64::INFINITY), &mut rec, rng) {
+                let weight: f64 = scattering_pdf.map_or(1_f64, |pdf| self.path_weight(&ray, pdf));
+                return radiance + weight * throughput * self.environment.radiance(&ray.direction);
+            }
+
+            // In fog, the ray may scatter on its way to the surface. The free-flight distance
+            // is exponentially distributed, and a scattering that happens first replaces the hit.
+            if self.fog_density > 0_f64 {
+                let ray_length: f64 = ray.direction.length();
+                let distance: f64 = -(1_f64 - rng.random_double()).ln() / self.fog_density;
+                if distance < rec.t * ray_length {
+                    rec.t = distance / ray_length;
+                    rec.p = ray.at(rec.t);
+                    rec.normal = Vec3::new(1_f64, 0_f64, 0_f64);
+                    rec.front_face = true;
+                    (rec.u, rec.v) = (0_f64, 0_f64);
+                    rec.mat = Some(Arc::clone(&self.fog_phase));
+                }
+            }
+
+            let Some(mat) = &rec.mat else {
+                return radiance;
+            };
+            let emitted: Color = mat.emitted(rec.u, rec.v, &rec.p);
+            if emitted != Color::default() {
+                let weight: f64 = match scattering_pdf {
+                    Some(pdf) if self.is_light(&ray, &rec, rng) => self.path_weight(&ray, pdf),
+                    _ => 1_f64,
+                };
+                radiance += weight * throughput * emitted;
+            }
+
+            let mut scattered: Ray = Ray::default();
+            let mut attenuation: Color = Color::default();
+            if !mat.scatter(&ray, &rec, &mut attenuation, &mut scattered, rng) {
+                return radiance;
+            }
+
+            let pdf: f64 = mat.scattering_pdf(&ray, &rec, &scattered);
+            scattering_pdf = None;
+            if pdf > 0_f64 && self.light_fraction.is_some() {
+                radiance += throughput * attenuation * self.sample_light(world, &ray, &rec, mat.as_ref(), rng);
+                scattering_pdf = Some(pdf);
+            }
+
+            throughput *= attenuation;
+            ray = scattered;
+        }
+
+        // If we've exceeded the ray bounce limit, no more light is gathered.
+        radiance
+    }
+
+    // Density with which `sample_light` draws directions from `origin`, or None when there
+    // is nothing to sample.
+    fn light_pdf(&self, origin: &Point3, time: f64) -> Option<MixturePdf<HittablePdf<'_>, EnvironmentPdf<'_>>> {
+        self.light_fraction.map(|fraction| MixturePdf::new(
+            HittablePdf::new(self.lights.as_ref(), *origin, time),
+            EnvironmentPdf::new(self.environment.as_ref()),
+            fraction,
+        ))
+    }
+
+    // Samples the light arriving directly at the hit `rec` from a direction aimed at the
+    // lights or the environment. The result is relative to the material's `attenuation`,
+    // and weighted against the chance of the material's own sample finding the same light.
+    fn sample_light<T: Hittable>(
+        &self,
+        world: &T,
+        r_in: &Ray,
+        rec: &HitRecord,
+        mat: &dyn Material,
+        rng: &mut Sampler
+    ) -> Color {
+        let Some(pdf) = self.light_pdf(&rec.p, r_in.time) else {
+            return Color::default();
+        };
+        let direction: Vec3 = Vec3::unit_vector(pdf.generate(rng));
+        let shadow_ray = Ray::with_time(rec.p, direction, r_in.time);
+
+        // Lights without area, which could never be hit, have an infinite density.
+        let light_pdf: f64 = pdf.value(&direction);
+        let scattering_pdf: f64 = mat.scattering_pdf(r_in, rec, &shadow_ray);
+        if !(light_pdf > 0_f64 && light_pdf.is_finite()) || scattering_pdf <= 0_f64 {
+            return Color::default();
         }
 
-        let unit_direction = Vec3::unit_vector(r.direction);
-        let a = 0.5 * (unit_direction.y() + 1.0);
+        // The light comes from the nearest light source along the direction, or else from
+        // the environment. Anything in front of it, including other lights, casts a shadow.
+        let mut light_rec: HitRecord = HitRecord::default();
+        let found: bool = self.lights.hit(&shadow_ray, &Interval::new(0.001, f64::INFINITY), &mut light_rec, rng);
+        let (emitted, distance) = if found {
+            let emitted: Option<Color> = light_rec.mat.map(|mat| mat.emitted(light_rec.u, light_rec.v, &light_rec.p));
+            (emitted.unwrap_or_default(), light_rec.t)
+        } else {
+            (self.environment.radiance(&direction), f64::INFINITY)
+        };
+        if emitted == Color::default() {
+            return Color::default();
+        }
+
+        // Fog ends at the edge of the scene, as it does for paths.
+        let mut transmittance: f64 = world.transmittance(&shadow_ray, &Interval::new(0.001, distance - 0.001), rng);
+        if self.fog_density > 0_f64 && distance.is_finite() {
+            transmittance *= (-self.fog_density * distance).exp();
+        }
+
+        power_heuristic(light_pdf, scattering_pdf) * scattering_pdf / light_pdf * transmittance * emitted
+    }
+
+    // Whether the emitter hit at `rec` is one of the lights, which `sample_light` could
+    // have found as well. Emitters left out of the lights, and glowing media, are found
+    // only by paths.
+    fn is_light(&self, ray: &Ray, rec: &HitRecord, rng: &mut Sampler) -> bool {
+        let mut light_rec: HitRecord = HitRecord::default();
+        self.lights.hit(ray, &Interval::new(0.001, f64::INFINITY), &mut light_rec, rng)
+            && (light_rec.t - rec.t).abs() <= 1e-9 * rec.t.max(1_f64)
+    }
 
-        (1.0 - a) * Color::new(1_f64, 1_f64, 1_f64) + a * Color::new(0.5, 0.7, 1.0)
+    // Weight of light that a path found by following a material sample drawn with density
+    // `scattering_pdf`, against the chance of `sample_light` finding it.
+    fn path_weight(&self, ray: &Ray, scattering_pdf: f64) -> f64 {
+        let light_pdf: f64 = self.light_pdf(&ray.origin, ray.time).map_or(0_f64, |pdf| pdf.value(&ray.direction));
+        power_heuristic(scattering_pdf, light_pdf)
     }
 }
@@ -0,0 +1,22 @@
+use std::error::Error;
+use std::fmt;
+
+// A camera setup from which no view can be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraError {
+    // `look_from` and `look_at` are the same point, so there is no view direction.
+    NoViewDirection,
+    // `vup` is zero or parallel to the view direction, so it does not fix which way is up.
+    UpParallelToView,
+}
+
+impl fmt::Display for CameraError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CameraError::NoViewDirection => write!(f, "the camera looks from and at the same point"),
+            CameraError::UpParallelToView => write!(f, "the camera's up direction is parallel to its view direction"),
+        }
+    }
+}
+
+impl Error for CameraError {}
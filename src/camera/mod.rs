@@ -1 +1,4 @@
-pub mod camera;
\ No newline at end of file
+pub mod builder;
+#[allow(clippy::module_inception)]
+pub mod camera;
+pub mod error;
@@ -0,0 +1,182 @@
+// Command-line interface of the renderer binary.
+
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::camera::builder::CameraBuilder;
+use crate::output::transform::{OutputTransform, ToneMap};
+use crate::scene::loader::parse_aspect_ratio;
+
+pub const USAGE: &str = "\
+Usage: ray_tracing [OPTIONS]
+
+Renders a scene file (or the built-in three spheres scene) to an image. Options given on
+the command line override the scene's render settings.
+
+Options:
+  -i, --scene <FILE>       Scene description to render
+  -o, --output <FILE>      Output image; the format follows the extension (png, jpg, ppm,
+                           exr, hdr, ...) [default: out/<aspect>_<width>_<spp>.png]
+  -w, --width <PIXELS>     Image width
+      --height <PIXELS>    Image height; sets the aspect ratio together with --width
+  -a, --aspect <RATIO>     Aspect ratio, as a number or W:H (e.g. 16:9)
+  -s, --spp <COUNT>        Samples per pixel
+  -d, --depth <COUNT>      Maximum number of bounces per path
+      --seed <SEED>        Random seed; equal seeds give identical images
+  -j, --threads <COUNT>    Worker threads [default: all cores]
+      --exposure <STOPS>   Exposure adjustment for LDR output
+      --tonemap <MAP>      Tone mapping for LDR output: none, reinhard or aces
+  -h, --help               Print this help
+";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Options {
+    pub scene: Option<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub aspect: Option<f64>,
+    pub samples: Option<u32>,
+    pub depth: Option<u32>,
+    pub seed: Option<u64>,
+    pub threads: Option<usize>,
+    pub exposure: Option<f64>,
+    pub tone_map: Option<ToneMap>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Render(Options),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CliError(pub String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for CliError {}
+
+// Parses the arguments following the program name.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, CliError> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        // Accept both `--flag value` and `--flag=value`.
+        let (flag, inline): (String, Option<String>) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg, None),
+        };
+        if flag == "-h" || flag == "--help" {
+            return Ok(Command::Help);
+        }
+
+        let mut value = || -> Result<String, CliError> {
+            match inline.clone().or_else(|| args.next()) {
+                Some(value) => Ok(value),
+                None => Err(CliError(format!("missing value for '{}'", flag))),
+            }
+        };
+
+        match flag.as_str() {
+            "-i" | "--scene" => options.scene = Some(PathBuf::from(value()?)),
+            "-o" | "--output" => options.output = Some(PathBuf::from(value()?)),
+            "-w" | "--width" => options.width = Some(positive(&flag, &value()?)?),
+            "--height" => options.height = Some(positive(&flag, &value()?)?),
+            "-a" | "--aspect" => {
+                let value = value()?;
+                match parse_aspect_ratio(&value) {
+                    Some(aspect) => options.aspect = Some(aspect),
+                    None => return Err(CliError(format!("invalid value '{}' for '{}'", value, flag))),
+                }
+            }
+            "-s" | "--spp" => options.samples = Some(positive(&flag, &value()?)?),
+            "-d" | "--depth" => options.depth = Some(number(&flag, &value()?)?),
+            "--seed" => options.seed = Some(number(&flag, &value()?)?),
+            "-j" | "--threads" => options.threads = Some(number(&flag, &value()?)?),
+            "--exposure" => options.exposure = Some(number(&flag, &value()?)?),
+            "--tonemap" => {
+                options.tone_map = Some(match value()?.as_str() {
+                    "none" => ToneMap::None,
+                    "reinhard" => ToneMap::Reinhard,
+                    "aces" => ToneMap::AcesFilmic,
+                    other => return Err(CliError(format!("unknown tone map '{}'", other))),
+                })
+            }
+            _ => return Err(CliError(format!("unrecognized argument '{}'", flag))),
+        }
+    }
+
+    if options.width.is_some() && options.height.is_some() && options.aspect.is_some() {
+        return Err(CliError("give at most two of '--width', '--height' and '--aspect'".to_string()));
+    }
+    if options.height.is_some() && options.width.is_none() && options.aspect.is_none() {
+        return Err(CliError("'--height' requires '--width' or '--aspect'".to_string()));
+    }
+
+    Ok(Command::Render(options))
+}
+
+fn number<T: FromStr>(flag: &str, value: &str) -> Result<T, CliError> {
+    value.parse::<T>().map_err(|_| CliError(format!("invalid value '{}' for '{}'", value, flag)))
+}
+
+fn positive(flag: &str, value: &str) -> Result<u32, CliError> {
+    match number::<u32>(flag, value)? {
+        0 => Err(CliError(format!("'{}' must be at least 1", flag))),
+        n => Ok(n),
+    }
+}
+
+impl Options {
+    // Applies the render settings given on the command line on top of the scene's.
+    pub fn apply_to_camera(&self, mut camera: CameraBuilder) -> CameraBuilder {
+        match (self.width, self.height, self.aspect) {
+            (Some(width), Some(height), _) => {
+                camera = camera.image_width(width).aspect_ratio(width as f64 / height as f64);
+            }
+            (None, Some(height), Some(aspect)) => {
+                let width: u32 = ((height as f64 * aspect).round() as u32).max(1);
+                camera = camera.image_width(width).aspect_ratio(aspect);
+            }
+            (width, _, aspect) => {
+                if let Some(width) = width {
+                    camera = camera.image_width(width);
+                }
+                if let Some(aspect) = aspect {
+                    camera = camera.aspect_ratio(aspect);
+                }
+            }
+        }
+        if let Some(samples) = self.samples {
+            camera = camera.samples_per_pixel(samples);
+        }
+        if let Some(depth) = self.depth {
+            camera = camera.max_depth(depth);
+        }
+        if let Some(seed) = self.seed {
+            camera = camera.seed(seed);
+        }
+        if let Some(threads) = self.threads {
+            camera = camera.threads(threads);
+        }
+        camera
+    }
+
+    pub fn apply_to_output(&self, mut output: OutputTransform) -> OutputTransform {
+        if let Some(exposure) = self.exposure {
+            output.exposure = exposure;
+        }
+        if let Some(tone_map) = self.tone_map {
+            output.tone_map = tone_map;
+        }
+        output
+    }
+}
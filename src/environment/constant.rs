@@ -0,0 +1,25 @@
+use crate::geometry::vec3::{Color, Vec3};
+
+use super::environment::Environment;
+
+// The same radiance from every direction. A black environment leaves a scene lit only by
+// its emissive materials.
+pub struct ConstantEnvironment {
+    pub color: Color,
+}
+
+impl ConstantEnvironment {
+    pub fn new(color: Color) -> Self {
+        Self { color }
+    }
+}
+
+impl Environment for ConstantEnvironment {
+    fn radiance(&self, _direction: &Vec3) -> Color {
+        self.color
+    }
+
+    fn is_black(&self) -> bool {
+        self.color == Color::default()
+    }
+}
@@ -0,0 +1,30 @@
+use std::f64::consts::PI;
+
+use crate::geometry::vec3::{Color, Vec3};
+
+use crate::sampling::sampler::Sampler;
+
+// Light arriving from infinitely far away, seen by every ray that leaves the scene.
+pub trait Environment: Send + Sync {
+    // Radiance arriving along `direction`, which points away from the scene and need not
+    // have unit length.
+    fn radiance(&self, direction: &Vec3) -> Color;
+
+    // Returns a unit direction drawn with density `pdf_value`. Environments with bright
+    // spots override both to send more samples towards them; by default directions are
+    // uniform over the sphere.
+    fn random(&self, rng: &mut Sampler) -> Vec3 {
+        Vec3::random_unit_vector(rng)
+    }
+
+    // Density of `random` choosing `direction`, with respect to solid angle.
+    fn pdf_value(&self, _direction: &Vec3) -> f64 {
+        1_f64 / (4_f64 * PI)
+    }
+
+    // Whether no light at all arrives from the environment, so that sampling it would be
+    // wasted.
+    fn is_black(&self) -> bool {
+        false
+    }
+}
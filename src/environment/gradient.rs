@@ -0,0 +1,29 @@
+use crate::geometry::vec3::{Color, Vec3};
+
+use super::environment::Environment;
+
+// A vertical blend from `bottom`, straight down, to `top`, straight up.
+pub struct GradientEnvironment {
+    pub bottom: Color,
+    pub top: Color,
+}
+
+impl GradientEnvironment {
+    pub fn new(bottom: Color, top: Color) -> Self {
+        Self { bottom, top }
+    }
+
+    // The white to light blue sky the renderer has always used.
+    pub fn sky() -> Self {
+        Self::new(Color::new(1.0, 1.0, 1.0), Color::new(0.5, 0.7, 1.0))
+    }
+}
+
+impl Environment for GradientEnvironment {
+    fn radiance(&self, direction: &Vec3) -> Color {
+        let unit_direction: Vec3 = Vec3::unit_vector(*direction);
+        let a: f64 = 0.5 * (unit_direction.y() + 1_f64);
+
+        (1_f64 - a) * self.bottom + a * self.top
+    }
+}
@@ -0,0 +1,99 @@
+use std::f64::consts::PI;
+use std::path::Path;
+
+use image::ImageResult;
+
+use crate::geometry::vec3::{Color, Vec3};
+
+use crate::sampling::distribution::Distribution2D;
+use crate::sampling::sampler::Sampler;
+
+use crate::textures::image_texture::ImageTexture;
+
+use super::environment::Environment;
+
+// An equirectangular (latitude-longitude) environment map, usually a linear HDR image. The
+// top row of the image is straight up and the center column faces -z, the default viewing
+// direction; `rotation` turns the map about the vertical axis. Each texel covers a constant
+// patch of the sphere, and `random` chooses texels in proportion to their luminance
+// times the solid angle they cover, so small bright lights such as the sun are found
+// quickly.
+pub struct HdriEnvironment {
+    image: ImageTexture,
+    // Rotation about +y in radians, and the multiplier applied to the texels.
+    rotation: f64,
+    intensity: f64,
+    distribution: Distribution2D,
+}
+
+impl HdriEnvironment {
+    // `rotation` is in degrees.
+    pub fn new(image: ImageTexture, rotation: f64, intensity: f64) -> Self {
+        let (width, height) = (image.width(), image.height());
+
+        let mut weights: Vec<f64> = Vec::with_capacity(width as usize * height as usize);
+        for j in 0..height {
+            let sin_theta: f64 = (PI * (j as f64 + 0.5) / height as f64).sin();
+            for i in 0..width {
+                weights.push(luminance(image.texel(i, j)) * sin_theta);
+            }
+        }
+        let distribution = Distribution2D::new(&weights, width as usize, height as usize);
+
+        Self { image, rotation: rotation.to_radians(), intensity, distribution }
+    }
+
+    pub fn load(path: &Path, rotation: f64, intensity: f64) -> ImageResult<Self> {
+        Ok(Self::new(ImageTexture::load(path)?, rotation, intensity))
+    }
+
+    // Image coordinates in [0,1)² of a direction: u across, t down from the top row.
+    fn direction_to_image(&self, direction: &Vec3) -> [f64; 2] {
+        let d: Vec3 = rotate_y(&Vec3::unit_vector(*direction), -self.rotation);
+        let u: f64 = 0.5 + d.x().atan2(-d.z()) / (2_f64 * PI);
+        let t: f64 = d.y().clamp(-1_f64, 1_f64).acos() / PI;
+        [u.rem_euclid(1_f64), t.min(1_f64 - f64::EPSILON)]
+    }
+
+    fn image_to_direction(&self, [u, t]: [f64; 2]) -> Vec3 {
+        let phi: f64 = (u - 0.5) * 2_f64 * PI;
+        let theta: f64 = t * PI;
+        let d = Vec3::new(theta.sin() * phi.sin(), theta.cos(), -theta.sin() * phi.cos());
+        rotate_y(&d, self.rotation)
+    }
+}
+
+impl Environment for HdriEnvironment {
+    fn radiance(&self, direction: &Vec3) -> Color {
+        let [u, t] = self.direction_to_image(direction);
+        let i: u32 = ((u * self.image.width() as f64) as u32).min(self.image.width() - 1);
+        let j: u32 = ((t * self.image.height() as f64) as u32).min(self.image.height() - 1);
+        self.intensity * self.image.texel(i, j)
+    }
+
+    fn random(&self, rng: &mut Sampler) -> Vec3 {
+        let (point, _) = self.distribution.sample([rng.random_double(), rng.random_double()]);
+        self.image_to_direction(point)
+    }
+
+    fn pdf_value(&self, direction: &Vec3) -> f64 {
+        let point: [f64; 2] = self.direction_to_image(direction);
+        let sin_theta: f64 = (point[1] * PI).sin();
+        if sin_theta <= 0_f64 {
+            return 0_f64;
+        }
+
+        // The image covers 2π by π radians, and a patch at polar angle θ shrinks by sin θ.
+        self.distribution.pdf(point) / (2_f64 * PI * PI * sin_theta)
+    }
+}
+
+fn luminance(c: Color) -> f64 {
+    0.2126 * c.x() + 0.7152 * c.y() + 0.0722 * c.z()
+}
+
+// Rotates `v` by `angle` radians about +y, following the right-hand rule.
+fn rotate_y(v: &Vec3, angle: f64) -> Vec3 {
+    let (sin, cos) = angle.sin_cos();
+    Vec3::new(cos * v.x() + sin * v.z(), v.y(), -sin * v.x() + cos * v.z())
+}
@@ -0,0 +1,6 @@
+pub mod constant;
+#[allow(clippy::module_inception)]
+pub mod environment;
+pub mod gradient;
+pub mod hdri;
+pub mod sun_sky;
@@ -0,0 +1,185 @@
+use std::f64::consts::PI;
+
+use crate::geometry::onb::Onb;
+use crate::geometry::vec3::{Color, Vec3};
+
+use crate::sampling::sampler::Sampler;
+use crate::sampling::warp;
+
+use super::environment::Environment;
+
+// Scale from the Preetham model's luminance, in kcd/m², to scene radiance.
+const SKY_SCALE: f64 = 0.05;
+// Fraction of `random` samples aimed at the sun.
+const SUN_SAMPLE_FRACTION: f64 = 0.5;
+// Fraction of the light at the horizon reflected back up by the ground.
+const GROUND_ALBEDO: f64 = 0.3;
+
+// An analytic daylight sky after Preetham, Shirley and Smits, "A Practical Analytic Model
+// for Daylight" (1999), with a sun disk. `turbidity` measures haze, from about 2 on a clear
+// day to 10 on a hazy one. The sun's brightness is given as the irradiance it delivers to a
+// surface facing it, and its color is reddened by the air it shines through at low
+// elevations. Below the horizon the sky is replaced by a dim ground.
+pub struct SunSkyEnvironment {
+    sun_direction: Vec3,
+    // Cosine of the sun's angular radius, and the radiance of its disk.
+    cos_sun_radius: f64,
+    sun_radiance: Color,
+    intensity: f64,
+    // Perez coefficients for luminance Y and chromaticities x and y, and the zenith values
+    // the distribution is normalized to.
+    perez: [[f64; 5]; 3],
+    zenith: [f64; 3],
+}
+
+impl SunSkyEnvironment {
+    // Angles are in degrees; `azimuth` turns the sun about +y, starting from -z.
+    pub fn new(elevation: f64, azimuth: f64, turbidity: f64) -> Self {
+        let elevation: f64 = elevation.to_radians();
+        let azimuth: f64 = azimuth.to_radians();
+        let sun_direction = Vec3::new(
+            -elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+            -elevation.cos() * azimuth.cos(),
+        );
+
+        let t: f64 = turbidity.max(1_f64);
+        let perez: [[f64; 5]; 3] = [
+            [0.1787 * t - 1.4630, -0.3554 * t + 0.4275, -0.0227 * t + 5.3251, 0.1206 * t - 2.5771, -0.0670 * t + 0.3703],
+            [-0.0193 * t - 0.2592, -0.0665 * t + 0.0008, -0.0004 * t + 0.2125, -0.0641 * t - 0.8989, -0.0033 * t + 0.0452],
+            [-0.0167 * t - 0.2608, -0.0950 * t + 0.0092, -0.0079 * t + 0.2102, -0.0441 * t - 1.6537, -0.0109 * t + 0.0529],
+        ];
+
+        // Zenith luminance and chromaticity, as functions of the sun's zenith angle.
+        let theta_s: f64 = PI / 2_f64 - elevation.max(0_f64);
+        let chi: f64 = (4_f64 / 9_f64 - t / 120_f64) * (PI - 2_f64 * theta_s);
+        let cubic = |c: [f64; 4]| c[0] * theta_s.powi(3) + c[1] * theta_s.powi(2) + c[2] * theta_s + c[3];
+        let zenith: [f64; 3] = [
+            ((4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192).max(0_f64),
+            t * t * cubic([0.00166, -0.00375, 0.00209, 0.0])
+                + t * cubic([-0.02903, 0.06377, -0.03202, 0.00394])
+                + cubic([0.11693, -0.21196, 0.06052, 0.25886]),
+            t * t * cubic([0.00275, -0.00610, 0.00317, 0.0])
+                + t * cubic([-0.04214, 0.08970, -0.04153, 0.00516])
+                + cubic([0.15346, -0.26756, 0.06670, 0.26688]),
+        ];
+
+        Self {
+            sun_direction,
+            cos_sun_radius: 1_f64,
+            sun_radiance: Color::default(),
+            intensity: 1_f64,
+            perez,
+            zenith,
+        }
+        .sun(0.5, 5_f64)
+    }
+
+    // Sets the sun's angular diameter in degrees, and the irradiance it delivers. The real
+    // sun is about half a degree across; larger suns give softer shadows and less noise.
+    pub fn sun(mut self, diameter: f64, irradiance: f64) -> Self {
+        let radius: f64 = (0.5 * diameter).clamp(0.01, 90_f64).to_radians();
+        self.cos_sun_radius = radius.cos();
+
+        let solid_angle: f64 = 2_f64 * PI * (1_f64 - self.cos_sun_radius);
+        self.sun_radiance = irradiance / solid_angle * transmittance(self.sun_direction.y());
+        self
+    }
+
+    // Multiplier applied to the sky, not the sun.
+    pub fn intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    fn sky(&self, direction: &Vec3) -> Color {
+        // The model diverges at the horizon, so stay just above it.
+        let cos_theta: f64 = direction.y().max(0.001);
+        let cos_gamma: f64 = Vec3::dot(direction, &self.sun_direction).clamp(-1_f64, 1_f64);
+        let gamma: f64 = cos_gamma.acos();
+        let cos_theta_s: f64 = self.sun_direction.y().max(0_f64);
+        let theta_s: f64 = cos_theta_s.acos();
+
+        let perez = |c: &[f64; 5], cos_theta: f64, gamma: f64, cos_gamma: f64| -> f64 {
+            (1_f64 + c[0] * (c[1] / cos_theta).exp())
+                * (1_f64 + c[2] * (c[3] * gamma).exp() + c[4] * cos_gamma * cos_gamma)
+        };
+        let value = |k: usize| -> f64 {
+            let c: &[f64; 5] = &self.perez[k];
+            self.zenith[k] * perez(c, cos_theta, gamma, cos_gamma) / perez(c, 1_f64, theta_s, cos_theta_s)
+        };
+
+        xyy_to_rgb(value(1), value(2), SKY_SCALE * value(0))
+    }
+}
+
+impl Environment for SunSkyEnvironment {
+    fn radiance(&self, direction: &Vec3) -> Color {
+        let direction: Vec3 = Vec3::unit_vector(*direction);
+
+        if direction.y() < 0_f64 {
+            let horizon: Vec3 = Vec3::unit_vector(Vec3::new(direction.x(), 0_f64, direction.z()));
+            return GROUND_ALBEDO * self.intensity * self.sky(&horizon);
+        }
+
+        let mut radiance: Color = self.intensity * self.sky(&direction);
+        if Vec3::dot(&direction, &self.sun_direction) >= self.cos_sun_radius {
+            radiance += self.sun_radiance;
+        }
+        radiance
+    }
+
+    fn random(&self, rng: &mut Sampler) -> Vec3 {
+        if rng.random_double() >= SUN_SAMPLE_FRACTION {
+            return Vec3::random_unit_vector(rng);
+        }
+
+        // Uniform over the cone of directions subtended by the sun.
+        let cone: Vec3 = warp::square_to_uniform_cone(rng.random_2d(), self.cos_sun_radius);
+        Onb::new(&self.sun_direction).transform(&cone)
+    }
+
+    fn pdf_value(&self, direction: &Vec3) -> f64 {
+        let uniform: f64 = warp::uniform_sphere_pdf();
+        let cos_angle: f64 = Vec3::dot(&Vec3::unit_vector(*direction), &self.sun_direction);
+        let cone: f64 = if cos_angle >= self.cos_sun_radius {
+            warp::uniform_cone_pdf(self.cos_sun_radius)
+        } else {
+            0_f64
+        };
+
+        SUN_SAMPLE_FRACTION * cone + (1_f64 - SUN_SAMPLE_FRACTION) * uniform
+    }
+}
+
+// Fraction of sunlight reaching the ground at a sun elevation with the given sine, from a
+// rough per-channel optical depth and the Kasten-Young air mass.
+fn transmittance(sin_elevation: f64) -> Color {
+    if sin_elevation <= 0_f64 {
+        return Color::default();
+    }
+    let elevation: f64 = sin_elevation.asin().to_degrees();
+    let air_mass: f64 = 1_f64 / (sin_elevation + 0.50572 * (elevation + 6.07995).powf(-1.6364));
+
+    let depth: [f64; 3] = [0.05, 0.1, 0.25];
+    Color::new(
+        (-depth[0] * air_mass).exp(),
+        (-depth[1] * air_mass).exp(),
+        (-depth[2] * air_mass).exp(),
+    )
+}
+
+// Converts CIE xyY to linear sRGB.
+fn xyy_to_rgb(x: f64, y: f64, luminance: f64) -> Color {
+    if y <= 0_f64 {
+        return Color::default();
+    }
+    let big_x: f64 = x * luminance / y;
+    let big_z: f64 = (1_f64 - x - y) * luminance / y;
+
+    Color::new(
+        (3.2406 * big_x - 1.5372 * luminance - 0.4986 * big_z).max(0_f64),
+        (-0.9689 * big_x + 1.8758 * luminance + 0.0415 * big_z).max(0_f64),
+        (0.0557 * big_x - 0.2040 * luminance + 1.0570 * big_z).max(0_f64),
+    )
+}
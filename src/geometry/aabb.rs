@@ -0,0 +1,124 @@
+use crate::geometry::interval::Interval;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+// Axis-aligned bounding box, stored as one interval per axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub x: Interval,
+    pub y: Interval,
+    pub z: Interval,
+}
+
+impl Default for Aabb {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl Aabb {
+    pub const EMPTY: Aabb = Aabb { x: Interval::EMPTY, y: Interval::EMPTY, z: Interval::EMPTY };
+    pub const UNIVERSE: Aabb = Aabb { x: Interval::UNIVERSE, y: Interval::UNIVERSE, z: Interval::UNIVERSE };
+
+    pub fn new(x: Interval, y: Interval, z: Interval) -> Self {
+        Self { x, y, z }.pad_to_minimums()
+    }
+
+    // Treat the two points a and b as extrema for the bounding box, so we don't require a
+    // particular minimum/maximum coordinate order.
+    pub fn from_points(a: Point3, b: Point3) -> Self {
+        Self::new(
+            Interval::new(a.x().min(b.x()), a.x().max(b.x())),
+            Interval::new(a.y().min(b.y()), a.y().max(b.y())),
+            Interval::new(a.z().min(b.z()), a.z().max(b.z())),
+        )
+    }
+
+    // Create the box tightly enclosing the two input boxes.
+    pub fn enclosing(box0: &Aabb, box1: &Aabb) -> Self {
+        Self {
+            x: Interval::enclosing(&box0.x, &box1.x),
+            y: Interval::enclosing(&box0.y, &box1.y),
+            z: Interval::enclosing(&box0.z, &box1.z),
+        }
+    }
+
+    pub fn axis_interval(&self, n: usize) -> &Interval {
+        match n {
+            1 => &self.y,
+            2 => &self.z,
+            _ => &self.x,
+        }
+    }
+
+    pub fn hit(&self, r: &Ray, ray_t: Interval) -> bool {
+        self.clip(r, ray_t).is_some()
+    }
+
+    // Returns the part of `ray_t` over which the ray is inside the box, if any.
+    pub fn clip(&self, r: &Ray, ray_t: Interval) -> Option<Interval> {
+        // Slab test: clip the ray interval against each pair of axis planes in turn.
+        let mut ray_t: Interval = ray_t;
+        for axis in 0..3 {
+            let ax: &Interval = self.axis_interval(axis);
+            let adinv: f64 = 1_f64 / r.direction[axis];
+
+            let t0: f64 = (ax.min - r.origin[axis]) * adinv;
+            let t1: f64 = (ax.max - r.origin[axis]) * adinv;
+
+            let (t0, t1) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+            if t0 > ray_t.min {
+                ray_t.min = t0;
+            }
+            if t1 < ray_t.max {
+                ray_t.max = t1;
+            }
+
+            if ray_t.max <= ray_t.min {
+                return None;
+            }
+        }
+        Some(ray_t)
+    }
+
+    // Returns the index of the longest axis of the bounding box.
+    pub fn longest_axis(&self) -> usize {
+        if self.x.size() > self.y.size() {
+            if self.x.size() > self.z.size() { 0 } else { 2 }
+        } else if self.y.size() > self.z.size() {
+            1
+        } else {
+            2
+        }
+    }
+
+    pub fn centroid(&self) -> Point3 {
+        0.5 * Vec3::new(self.x.min + self.x.max, self.y.min + self.y.max, self.z.min + self.z.max)
+    }
+
+    // False for boxes reaching infinity along some axis, such as those of infinite planes.
+    pub fn is_bounded(&self) -> bool {
+        [self.x, self.y, self.z].iter().all(|axis| axis.min.is_finite() && axis.max.is_finite())
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        let (dx, dy, dz) = (self.x.size(), self.y.size(), self.z.size());
+        if dx < 0_f64 || dy < 0_f64 || dz < 0_f64 {
+            return 0_f64;
+        }
+        2_f64 * (dx * dy + dy * dz + dz * dx)
+    }
+
+    // Adjust the AABB so that no side is narrower than some delta, padding if necessary.
+    // This keeps flat primitives such as quads hittable by the slab test.
+    fn pad_to_minimums(self) -> Self {
+        let delta: f64 = 0.0001;
+        let pad = |interval: Interval| if interval.size() < delta { interval.expand(delta) } else { interval };
+
+        Self {
+            x: pad(self.x),
+            y: pad(self.y),
+            z: pad(self.z),
+        }
+    }
+}
@@ -1,23 +1,31 @@
-//static empty: Interval = Interval::default();
-//static universe: Interval = Interval::new(f64::NEG_INFINITY, f64::INFINITY);
-
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Interval {
     pub min: f64,
     pub max: f64,
 }
 
-impl Interval {
-    pub fn default() -> Self {
-        Self {
-            min: f64::INFINITY,
-            max: f64::NEG_INFINITY,
-        }
+impl Default for Interval {
+    fn default() -> Self {
+        Self::EMPTY
     }
+}
+
+impl Interval {
+    pub const EMPTY: Interval = Interval { min: f64::INFINITY, max: f64::NEG_INFINITY };
+    pub const UNIVERSE: Interval = Interval { min: f64::NEG_INFINITY, max: f64::INFINITY };
 
     pub fn new(min: f64, max: f64) -> Self {
         Self { min, max }
     }
 
+    // Create the interval tightly enclosing the two input intervals.
+    pub fn enclosing(a: &Interval, b: &Interval) -> Self {
+        Self {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        }
+    }
+
     pub fn size(&self) -> f64 {
         self.max - self.min
     }
@@ -40,4 +48,10 @@ impl Interval {
 
         x
     }
+
+    // Pad the interval by `delta / 2` on each side.
+    pub fn expand(&self, delta: f64) -> Self {
+        let padding: f64 = delta / 2_f64;
+        Self::new(self.min - padding, self.max + padding)
+    }
 }
@@ -0,0 +1,76 @@
+use std::ops;
+
+use crate::geometry::vec3::Vec3;
+
+// A 3x3 matrix, stored row-major, acting on column vectors.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mat3 {
+    pub m: [[f64; 3]; 3],
+}
+
+impl Default for Mat3 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat3 {
+    pub const IDENTITY: Mat3 = Mat3 {
+        m: [
+            [1_f64, 0_f64, 0_f64],
+            [0_f64, 1_f64, 0_f64],
+            [0_f64, 0_f64, 1_f64],
+        ],
+    };
+
+    pub fn new(m: [[f64; 3]; 3]) -> Self {
+        Self { m }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::new(std::array::from_fn(|i| std::array::from_fn(|j| self.m[j][i])))
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    // Returns the inverse, or None if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let det: f64 = self.determinant();
+        if det == 0_f64 || !det.is_finite() {
+            return None;
+        }
+
+        // The inverse is the transposed matrix of cofactors, over the determinant.
+        let m = &self.m;
+        let cofactor = |i: usize, j: usize| -> f64 {
+            let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+            let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        Some(Self::new(std::array::from_fn(|i| std::array::from_fn(|j| cofactor(j, i) / det))))
+    }
+}
+
+impl ops::Mul<Mat3> for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, rhs: Mat3) -> Self::Output {
+        Mat3::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| (0..3).map(|k| self.m[i][k] * rhs.m[k][j]).sum())
+        }))
+    }
+}
+
+impl ops::Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, rhs: Vec3) -> Self::Output {
+        let row = |i: usize| self.m[i][0] * rhs.e[0] + self.m[i][1] * rhs.e[1] + self.m[i][2] * rhs.e[2];
+        Vec3::new(row(0), row(1), row(2))
+    }
+}
@@ -0,0 +1,148 @@
+use std::ops;
+
+use crate::geometry::mat3::Mat3;
+use crate::geometry::vec3::{Point3, Vec3};
+
+// A 4x4 affine or projective transform, stored row-major, acting on column vectors in
+// homogeneous coordinates. Composition reads right to left: `a * b` applies `b` first.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mat4 {
+    pub m: [[f64; 4]; 4],
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat4 {
+    pub const IDENTITY: Mat4 = Mat4 {
+        m: [
+            [1_f64, 0_f64, 0_f64, 0_f64],
+            [0_f64, 1_f64, 0_f64, 0_f64],
+            [0_f64, 0_f64, 1_f64, 0_f64],
+            [0_f64, 0_f64, 0_f64, 1_f64],
+        ],
+    };
+
+    pub fn new(m: [[f64; 4]; 4]) -> Self {
+        Self { m }
+    }
+
+    // The affine transform applying `linear`, then moving by `offset`.
+    pub fn from_parts(linear: &Mat3, offset: Vec3) -> Self {
+        let mut m: [[f64; 4]; 4] = Self::IDENTITY.m;
+        for i in 0..3 {
+            m[i][..3].copy_from_slice(&linear.m[i]);
+            m[i][3] = offset[i];
+        }
+        Self::new(m)
+    }
+
+    pub fn translation(offset: Vec3) -> Self {
+        Self::from_parts(&Mat3::IDENTITY, offset)
+    }
+
+    pub fn scaling(factors: Vec3) -> Self {
+        let mut linear: Mat3 = Mat3::IDENTITY;
+        for i in 0..3 {
+            linear.m[i][i] = factors[i];
+        }
+        Self::from_parts(&linear, Vec3::default())
+    }
+
+    // Rotation by `degrees` about `axis`, following the right-hand rule.
+    pub fn rotation(axis: Vec3, degrees: f64) -> Self {
+        let a: Vec3 = Vec3::unit_vector(axis);
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let t: f64 = 1_f64 - cos;
+        let (x, y, z) = (a.x(), a.y(), a.z());
+
+        let linear = Mat3::new([
+            [t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y],
+            [t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x],
+            [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos],
+        ]);
+        Self::from_parts(&linear, Vec3::default())
+    }
+
+    pub fn rotation_x(degrees: f64) -> Self {
+        Self::rotation(Vec3::new(1_f64, 0_f64, 0_f64), degrees)
+    }
+
+    pub fn rotation_y(degrees: f64) -> Self {
+        Self::rotation(Vec3::new(0_f64, 1_f64, 0_f64), degrees)
+    }
+
+    pub fn rotation_z(degrees: f64) -> Self {
+        Self::rotation(Vec3::new(0_f64, 0_f64, 1_f64), degrees)
+    }
+
+    // The upper left 3x3 block, which acts on directions.
+    pub fn linear(&self) -> Mat3 {
+        Mat3::new(std::array::from_fn(|i| std::array::from_fn(|j| self.m[i][j])))
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::new(std::array::from_fn(|i| std::array::from_fn(|j| self.m[j][i])))
+    }
+
+    // Returns the inverse, or None if the matrix is singular. Uses Gauss-Jordan elimination
+    // with partial pivoting.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut a: [[f64; 4]; 4] = self.m;
+        let mut inv: [[f64; 4]; 4] = Self::IDENTITY.m;
+
+        for col in 0..4 {
+            let pivot: usize = (col..4).max_by(|&r, &s| a[r][col].abs().total_cmp(&a[s][col].abs()))?;
+            if a[pivot][col].abs() < 1e-12 || !a[pivot][col].is_finite() {
+                return None;
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let scale: f64 = 1_f64 / a[col][col];
+            for j in 0..4 {
+                a[col][j] *= scale;
+                inv[col][j] *= scale;
+            }
+
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let factor: f64 = a[row][col];
+                for j in 0..4 {
+                    a[row][j] -= factor * a[col][j];
+                    inv[row][j] -= factor * inv[col][j];
+                }
+            }
+        }
+
+        Some(Self::new(inv))
+    }
+
+    // Applies the transform to a point, dividing through by w for projective matrices.
+    pub fn transform_point(&self, p: &Point3) -> Point3 {
+        let row = |i: usize| self.m[i][0] * p.e[0] + self.m[i][1] * p.e[1] + self.m[i][2] * p.e[2] + self.m[i][3];
+        let w: f64 = row(3);
+        let p = Point3::new(row(0), row(1), row(2));
+        if w == 1_f64 { p } else { p / w }
+    }
+
+    // Applies the linear part of the transform to a direction, ignoring translation.
+    pub fn transform_vector(&self, v: &Vec3) -> Vec3 {
+        self.linear() * *v
+    }
+}
+
+impl ops::Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, rhs: Mat4) -> Self::Output {
+        Mat4::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| (0..4).map(|k| self.m[i][k] * rhs.m[k][j]).sum())
+        }))
+    }
+}
@@ -1,3 +1,7 @@
+pub mod aabb;
+pub mod interval;
+pub mod mat3;
+pub mod mat4;
+pub mod onb;
 pub mod ray;
 pub mod vec3;
-pub mod interval;
\ No newline at end of file
@@ -0,0 +1,34 @@
+use super::vec3::Vec3;
+
+// An orthonormal basis built around a given direction `w`, with `u` and `v` spanning the
+// plane perpendicular to it. The basis is right-handed, so `u × v = w`, and it depends only
+// on `w`, so equal directions always get the same `u` and `v`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Onb {
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+}
+
+impl Onb {
+    pub fn new(n: &Vec3) -> Self {
+        let w: Vec3 = Vec3::unit_vector(*n);
+
+        // Any vector not parallel to `w` gives a perpendicular by the cross product.
+        let a: Vec3 = if w.x().abs() > 0.9 { Vec3::new(0.0, 1.0, 0.0) } else { Vec3::new(1.0, 0.0, 0.0) };
+        let v: Vec3 = Vec3::unit_vector(Vec3::cross(&w, &a));
+        let u: Vec3 = Vec3::cross(&v, &w);
+
+        Self { u, v, w }
+    }
+
+    // Carries coordinates in this basis out to the vector they describe.
+    pub fn transform(&self, a: &Vec3) -> Vec3 {
+        a.x() * self.u + a.y() * self.v + a.z() * self.w
+    }
+
+    // The coordinates of `a` in this basis; the inverse of `transform`.
+    pub fn to_local(&self, a: &Vec3) -> Vec3 {
+        Vec3::new(Vec3::dot(a, &self.u), Vec3::dot(a, &self.v), Vec3::dot(a, &self.w))
+    }
+}
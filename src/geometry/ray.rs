@@ -1,16 +1,24 @@
 use crate::geometry::vec3::{Vec3, Point3};
 
+#[derive(Clone, Copy, Default)]
 pub struct Ray {
     pub origin: Point3,
     pub direction: Vec3,
+    // The instant the ray samples, within the camera's shutter interval. Moving objects are
+    // intersected where they are at this time.
+    pub time: f64,
 }
 
 impl Ray {
     pub fn new(origin: Point3, direction: Vec3) -> Self {
-        Self { origin, direction }
+        Self { origin, direction, time: 0_f64 }
+    }
+
+    pub fn with_time(origin: Point3, direction: Vec3, time: f64) -> Self {
+        Self { origin, direction, time }
     }
 
     pub fn at(&self, t: f64) -> Point3 {
         self.origin + t * self.direction
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,9 @@
 use std::ops;
 
-use crate::{random_double, random_double_range};
+use crate::sampling::sampler::Sampler;
+use crate::sampling::warp;
+
+use super::onb::Onb;
 
 pub type Point3 = Vec3;
 pub type Color = Vec3;
@@ -10,59 +13,52 @@ pub struct Vec3 {
     pub e: [f64; 3],
 }
 
-impl Vec3 {
-    pub fn default() -> Self {
+impl Default for Vec3 {
+    fn default() -> Self {
         Self {
             e: [0_f64, 0_f64, 0_f64]
         }
     }
+}
 
+impl Vec3 {
     pub fn new(e0: f64, e1: f64, e2:f64) -> Self {
         Self {
             e: [e0, e1, e2]
         }
     }
 
-    pub fn random() -> Self {
+    pub fn random(rng: &mut Sampler) -> Self {
         Self {
             e: [
-                random_double(),
-                random_double(),
-                random_double()
+                rng.random_double(),
+                rng.random_double(),
+                rng.random_double()
             ],
         }
     }
 
-    pub fn random_range(min: f64, max: f64) -> Self {
+    pub fn random_range(rng: &mut Sampler, min: f64, max: f64) -> Self {
         Self {
             e: [
-                random_double_range(min, max),
-                random_double_range(min, max),
-                random_double_range(min, max)
+                rng.random_double_range(min, max),
+                rng.random_double_range(min, max),
+                rng.random_double_range(min, max)
             ],
         }
     }
 
-    fn random_in_unit_sphere() -> Vec3 {
-        loop {
-            let p = Self::random_range(-1_f64, 1_f64);
-            if p.length_squared() < 1_f64 { 
-                return p; 
-            }
-        }
+    pub fn random_in_unit_disk(rng: &mut Sampler) -> Vec3 {
+        warp::square_to_concentric_disk(rng.random_2d())
     }
 
-    pub fn random_on_hemisphere(normal: &Vec3) -> Self {
-        let on_unit_sphere: Vec3 = Self::random_unit_vector();
-        if Self::dot(&on_unit_sphere, normal) > 0_f64 {
-            return on_unit_sphere;
-        } else {
-            return -1_f64 * on_unit_sphere;
-        }
+    // Uniform over the hemisphere around `normal`.
+    pub fn random_on_hemisphere(rng: &mut Sampler, normal: &Vec3) -> Self {
+        Onb::new(normal).transform(&warp::square_to_uniform_hemisphere(rng.random_2d()))
     }
 
-    pub fn random_unit_vector() -> Vec3 {
-        Self::unit_vector(Self::random_in_unit_sphere())
+    pub fn random_unit_vector(rng: &mut Sampler) -> Vec3 {
+        warp::square_to_uniform_sphere(rng.random_2d())
     }
 
     pub fn x(&self) -> f64 {
@@ -77,6 +73,12 @@ impl Vec3 {
         self.e[2]
     }
 
+    pub fn near_zero(&self) -> bool {
+        // Return true if the vector is close to zero in all dimensions.
+        let s: f64 = 1e-8;
+        self.e[0].abs() < s && self.e[1].abs() < s && self.e[2].abs() < s
+    }
+
     pub fn length(&self) -> f64 {
         self.length_squared().sqrt()
     }
@@ -104,6 +106,18 @@ impl Vec3 {
     pub fn unit_vector(v: Vec3) -> Vec3 {
         (1_f64 / v.length()) * v
     }
+
+    pub fn reflect(v: &Vec3, n: &Vec3) -> Vec3 {
+        *v - 2_f64 * Self::dot(v, n) * *n
+    }
+
+    pub fn refract(uv: &Vec3, n: &Vec3, etai_over_etat: f64) -> Vec3 {
+        let cos_theta: f64 = Self::dot(&-*uv, n).min(1_f64);
+        let r_out_perp: Vec3 = etai_over_etat * (*uv + cos_theta * *n);
+        let r_out_parallel: Vec3 = -(1_f64 - r_out_perp.length_squared()).abs().sqrt() * *n;
+
+        r_out_perp + r_out_parallel
+    }
 }
 
 impl ops::Index<usize> for  Vec3 {
@@ -120,6 +134,14 @@ impl ops::IndexMut<usize> for Vec3 {
     }
 }
 
+impl ops::Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Self::Output {
+        Vec3::new(-self.e[0], -self.e[1], -self.e[2])
+    }
+}
+
 impl ops::Add<Vec3> for Vec3 {
     type Output = Vec3;
 
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::ray::Ray;
+
+use super::hittable::{HitRecord, Hittable};
+use super::hittable_list::HittableList;
+
+use crate::sampling::sampler::Sampler;
+
+// Number of centroid buckets evaluated by the surface area heuristic at each split.
+const SAH_BUCKETS: usize = 12;
+
+type Objects = Vec<Arc<dyn Hittable>>;
+
+// Bounding volume hierarchy node. Leaves hold a single object in both children. Unbounded
+// objects, which cannot be placed by their centroid, are kept in a subtree of their own
+// beside the tree of bounded ones.
+pub struct BvhNode {
+    left: Arc<dyn Hittable>,
+    right: Arc<dyn Hittable>,
+    bbox: Aabb,
+}
+
+impl BvhNode {
+    pub fn new(list: HittableList) -> Self {
+        Self::from_objects(list.objects().to_vec())
+    }
+
+    pub fn from_objects(objects: Objects) -> Self {
+        let (bounded, unbounded): (Objects, Objects) =
+            objects.iter().cloned().partition(|object| object.bounding_box().is_bounded());
+        if !bounded.is_empty() && !unbounded.is_empty() {
+            return Self::node(Self::subtree(bounded), Self::subtree(unbounded));
+        }
+
+        match objects.len() {
+            0 => {
+                let empty: Arc<dyn Hittable> = Arc::new(HittableList::new());
+                Self { left: Arc::clone(&empty), right: empty, bbox: Aabb::EMPTY }
+            }
+            1 => {
+                let object: Arc<dyn Hittable> = Arc::clone(&objects[0]);
+                Self { bbox: object.bounding_box(), left: Arc::clone(&object), right: object }
+            }
+            2 => Self::node(Arc::clone(&objects[0]), Arc::clone(&objects[1])),
+            _ => {
+                let (left, right) = Self::split(objects);
+                Self::node(Self::subtree(left), Self::subtree(right))
+            }
+        }
+    }
+
+    fn node(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>) -> Self {
+        let bbox: Aabb = Aabb::enclosing(&left.bounding_box(), &right.bounding_box());
+        Self { left, right, bbox }
+    }
+
+    fn subtree(objects: Objects) -> Arc<dyn Hittable> {
+        if objects.len() == 1 {
+            return Arc::clone(&objects[0]);
+        }
+        Arc::new(Self::from_objects(objects))
+    }
+
+    // Partitions the objects in two, using a binned surface area heuristic along the longest
+    // axis of the centroid bounds, and falling back to a median split when the heuristic
+    // cannot separate them.
+    fn split(mut objects: Objects) -> (Objects, Objects) {
+        let centroids: Aabb = objects.iter().fold(Aabb::EMPTY, |bounds, object| {
+            let c = object.bounding_box().centroid();
+            Aabb::enclosing(&bounds, &Aabb::from_points(c, c))
+        });
+        let axis: usize = centroids.longest_axis();
+        let extent: Interval = *centroids.axis_interval(axis);
+
+        if extent.size() > 0_f64 && extent.size().is_finite() {
+            let bucket = |object: &Arc<dyn Hittable>| -> usize {
+                let c: f64 = object.bounding_box().centroid()[axis];
+                let b: usize = (SAH_BUCKETS as f64 * (c - extent.min) / extent.size()) as usize;
+                b.min(SAH_BUCKETS - 1)
+            };
+
+            let mut counts = [0_usize; SAH_BUCKETS];
+            let mut bounds = [Aabb::EMPTY; SAH_BUCKETS];
+            for object in objects.iter() {
+                let b: usize = bucket(object);
+                counts[b] += 1;
+                bounds[b] = Aabb::enclosing(&bounds[b], &object.bounding_box());
+            }
+
+            // Cost of splitting after each bucket, up to a constant factor.
+            let mut best: Option<(usize, f64)> = None;
+            for split in 0..SAH_BUCKETS - 1 {
+                let (mut left_box, mut left_count) = (Aabb::EMPTY, 0);
+                let (mut right_box, mut right_count) = (Aabb::EMPTY, 0);
+                for b in 0..=split {
+                    left_box = Aabb::enclosing(&left_box, &bounds[b]);
+                    left_count += counts[b];
+                }
+                for b in split + 1..SAH_BUCKETS {
+                    right_box = Aabb::enclosing(&right_box, &bounds[b]);
+                    right_count += counts[b];
+                }
+                if left_count == 0 || right_count == 0 {
+                    continue;
+                }
+
+                let cost: f64 = left_box.surface_area() * left_count as f64
+                    + right_box.surface_area() * right_count as f64;
+                if best.is_none_or(|(_, best_cost)| cost < best_cost) {
+                    best = Some((split, cost));
+                }
+            }
+
+            if let Some((split, _)) = best {
+                return objects.into_iter().partition(|object| bucket(object) <= split);
+            }
+        }
+
+        let mid: usize = objects.len() / 2;
+        objects.select_nth_unstable_by(mid, |a, b| {
+            let a: f64 = a.bounding_box().centroid()[axis];
+            let b: f64 = b.bounding_box().centroid()[axis];
+            a.total_cmp(&b)
+        });
+        let right: Objects = objects.split_off(mid);
+        (objects, right)
+    }
+}
+
+impl Hittable for BvhNode {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        rng: &mut Sampler
+    ) -> bool {
+        if !self.bbox.hit(r, *ray_t) {
+            return false;
+        }
+
+        let hit_left: bool = self.left.hit(r, ray_t, rec, rng);
+        let right_t: Interval = Interval::new(ray_t.min, if hit_left { rec.t } else { ray_t.max });
+        let hit_right: bool = self.right.hit(r, &right_t, rec, rng);
+
+        hit_left || hit_right
+    }
+
+    fn transmittance(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> f64 {
+        if !self.bbox.hit(r, *ray_t) {
+            return 1_f64;
+        }
+
+        let left: f64 = self.left.transmittance(r, ray_t, rng);
+        if left == 0_f64 || Arc::ptr_eq(&self.left, &self.right) {
+            return left;
+        }
+        left * self.right.transmittance(r, ray_t, rng)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Color, Vec3};
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+
+use crate::materials::isotropic::Isotropic;
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+
+use crate::textures::texture::Texture;
+
+// A volume of uniform density filling a closed boundary, such as smoke or fog. A ray
+// crossing the volume travels a random, exponentially distributed distance before it
+// scatters, and passes through unaffected if that distance takes it out the other side.
+// The boundary must be convex, as only its first entry and exit points are considered.
+pub struct ConstantMedium {
+    boundary: Arc<dyn Hittable>,
+    neg_inv_density: f64,
+    phase_function: Arc<dyn Material>,
+}
+
+impl ConstantMedium {
+    pub fn new(boundary: Arc<dyn Hittable>, density: f64, albedo: Color) -> Self {
+        Self::from_material(boundary, density, Arc::new(Isotropic::new(albedo)))
+    }
+
+    pub fn from_texture(boundary: Arc<dyn Hittable>, density: f64, tex: Arc<dyn Texture>) -> Self {
+        Self::from_material(boundary, density, Arc::new(Isotropic::from_texture(tex)))
+    }
+
+    // A medium scattering with `phase_function`, normally an `Isotropic` material.
+    pub fn from_material(boundary: Arc<dyn Hittable>, density: f64, phase_function: Arc<dyn Material>) -> Self {
+        assert!(density > 0_f64, "medium density must be positive");
+        Self { boundary, neg_inv_density: -1_f64 / density, phase_function }
+    }
+
+    // The part of `ray_t` over which the ray is inside the boundary, found from where the
+    // ray's line enters and leaves it, even if the ray starts inside.
+    fn overlap(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> Option<(f64, f64)> {
+        let mut rec1: HitRecord = HitRecord::default();
+        let mut rec2: HitRecord = HitRecord::default();
+        if !self.boundary.hit(r, &Interval::UNIVERSE, &mut rec1, rng) {
+            return None;
+        }
+        if !self.boundary.hit(r, &Interval::new(rec1.t + 0.0001, f64::INFINITY), &mut rec2, rng) {
+            return None;
+        }
+
+        let t_enter: f64 = rec1.t.max(ray_t.min);
+        let t_exit: f64 = rec2.t.min(ray_t.max);
+        (t_enter < t_exit).then_some((t_enter, t_exit))
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        rng: &mut Sampler
+    ) -> bool {
+        let Some((t_enter, t_exit)) = self.overlap(r, ray_t, rng) else {
+            return false;
+        };
+
+        let ray_length: f64 = r.direction.length();
+        let distance_inside_boundary: f64 = (t_exit - t_enter) * ray_length;
+        let hit_distance: f64 = self.neg_inv_density * (1_f64 - rng.random_double()).ln();
+        if hit_distance > distance_inside_boundary {
+            return false;
+        }
+
+        rec.t = t_enter + hit_distance / ray_length;
+        rec.p = r.at(rec.t);
+
+        // Arbitrary; the phase function does not use them.
+        rec.normal = Vec3::new(1_f64, 0_f64, 0_f64);
+        rec.front_face = true;
+        (rec.u, rec.v) = (0_f64, 0_f64);
+        rec.mat = Some(Arc::clone(&self.phase_function));
+
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.boundary.bounding_box()
+    }
+
+    // Uniform density makes the transmittance exact: it falls off exponentially with the
+    // distance traveled inside the boundary.
+    fn transmittance(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> f64 {
+        match self.overlap(r, ray_t, rng) {
+            Some((t_enter, t_exit)) => ((t_exit - t_enter) * r.direction.length() / self.neg_inv_density).exp(),
+            None => 1_f64,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::onb::Onb;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+use crate::sampling::warp;
+
+// A flat disk facing along `normal`. Its (u, v) surface coordinates map the disk into the
+// unit square, with the center at (0.5, 0.5). The radius must not be negative.
+pub struct Disk {
+    pub center: Point3,
+    pub normal: Vec3,
+    pub radius: f64,
+    pub mat: Arc<dyn Material>,
+    frame: Onb,
+    bbox: Aabb,
+}
+
+impl Disk {
+    pub fn new(center: Point3, normal: Vec3, radius: f64, mat: Arc<dyn Material>) -> Self {
+        let frame = Onb::new(&normal);
+        let normal: Vec3 = frame.w;
+        assert!(radius >= 0_f64, "disk radius must not be negative, got {}", radius);
+
+        // Along each axis the disk extends by the radius, scaled by how much of that axis
+        // lies in the disk's plane.
+        let extent = |i: usize| -> f64 { radius * (1_f64 - normal[i] * normal[i]).max(0_f64).sqrt() };
+        let e: Vec3 = Vec3::new(extent(0), extent(1), extent(2));
+        let bbox: Aabb = Aabb::from_points(center - e, center + e);
+
+        Self { center, normal, radius, mat, frame, bbox }
+    }
+
+    // Returns the ray parameter of the hit and the hit point's offset from the center.
+    fn intersect(&self, r: &Ray, ray_t: &Interval) -> Option<(f64, Vec3)> {
+        let denom: f64 = Vec3::dot(&self.normal, &r.direction);
+        if denom.abs() < 1e-8 {
+            return None;
+        }
+
+        let t: f64 = Vec3::dot(&self.normal, &(self.center - r.origin)) / denom;
+        if !ray_t.surrounds(t) {
+            return None;
+        }
+
+        let offset: Vec3 = r.at(t) - self.center;
+        if offset.length_squared() > self.radius * self.radius {
+            return None;
+        }
+
+        Some((t, offset))
+    }
+}
+
+impl Hittable for Disk {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        _rng: &mut Sampler
+    ) -> bool {
+        let Some((t, offset)) = self.intersect(r, ray_t) else {
+            return false;
+        };
+
+        rec.t = t;
+        rec.p = self.center + offset;
+        rec.u = 0.5 + Vec3::dot(&offset, &self.frame.u) / (2_f64 * self.radius);
+        rec.v = 0.5 + Vec3::dot(&offset, &self.frame.v) / (2_f64 * self.radius);
+        rec.mat = Some(Arc::clone(&self.mat));
+        rec.set_face_normal(r, &self.normal);
+
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    // Points are uniform over the area, as for `Quad`.
+    fn pdf_value(&self, r: &Ray) -> f64 {
+        let Some((t, _)) = self.intersect(r, &Interval::new(0.001, f64::INFINITY)) else {
+            return 0_f64;
+        };
+
+        let distance_squared: f64 = t * t * r.direction.length_squared();
+        let cosine: f64 = Vec3::dot(&r.direction, &self.normal).abs() / r.direction.length();
+        distance_squared / (cosine * PI * self.radius * self.radius)
+    }
+
+    fn random(&self, origin: &Point3, _time: f64, rng: &mut Sampler) -> Vec3 {
+        let p: Vec3 = self.radius * warp::square_to_concentric_disk(rng.random_2d());
+        self.center + self.frame.transform(&p) - *origin
+    }
+}
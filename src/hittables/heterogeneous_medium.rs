@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Color, Point3, Vec3};
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+
+use crate::materials::isotropic::Isotropic;
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+
+use crate::volumes::density::DensityField;
+
+// A volume whose density varies from point to point, filling an axis-aligned box with a
+// density field stretched over it. `sigma` is the extinction per unit of density and unit of
+// distance; a fraction `albedo` of the light meeting the medium is scattered isotropically,
+// and the rest is absorbed.
+//
+// Collisions are sampled by delta tracking: tentative collisions are drawn against the
+// field's maximum density, and each is accepted as real with the ratio of the actual
+// density to that maximum. Transmittance is estimated by ratio tracking, which instead
+// multiplies together the chances of each tentative collision being fictitious.
+pub struct HeterogeneousMedium {
+    field: Arc<dyn DensityField>,
+    origin: Point3,
+    size: Vec3,
+    bbox: Aabb,
+    sigma: f64,
+    albedo: Color,
+    phase_function: Arc<dyn Material>,
+    emission: Option<(Arc<dyn DensityField>, Color)>,
+}
+
+impl HeterogeneousMedium {
+    pub fn new(field: Arc<dyn DensityField>, min: Point3, max: Point3, sigma: f64, albedo: Color) -> Self {
+        let size: Vec3 = max - min;
+        assert!((0..3).all(|axis| size[axis] > 0_f64), "volume bounds must have positive size");
+        assert!(sigma >= 0_f64, "volume extinction must not be negative");
+
+        Self {
+            field,
+            origin: min,
+            size,
+            bbox: Aabb::from_points(min, max),
+            sigma,
+            albedo,
+            phase_function: Arc::new(Isotropic::new(albedo)),
+            emission: None,
+        }
+    }
+
+    // Makes the medium glow, as fire does, with `color` scaled by `field`. The color is the
+    // radiance of an optically thick, black region where the field is one; thinner regions
+    // glow less, in proportion to the light they absorb. Only absorbed light is replaced by
+    // emission, so the glow is dimmed by `1 - albedo`, and a medium with an albedo of one
+    // does not glow at all.
+    pub fn with_emission(mut self, field: Arc<dyn DensityField>, color: Color) -> Self {
+        self.emission = Some((field, color));
+        self
+    }
+
+    // Maps a world space point into the unit cube of the fields.
+    fn local(&self, p: &Point3) -> Point3 {
+        let offset: Vec3 = *p - self.origin;
+        Point3::new(offset.x() / self.size.x(), offset.y() / self.size.y(), offset.z() / self.size.z())
+    }
+
+    // The density at `p` relative to the field's maximum, in [0,1].
+    fn density_ratio(&self, p: &Point3) -> f64 {
+        (self.field.density(&self.local(p)) / self.field.max_density()).min(1_f64)
+    }
+
+    // The part of `ray_t` inside the bounds, and the majorant collision rate per unit of the
+    // ray parameter there, if collisions are possible at all.
+    fn tracking_span(&self, r: &Ray, ray_t: &Interval) -> Option<(Interval, f64)> {
+        let rate: f64 = self.sigma * self.field.max_density() * r.direction.length();
+        if rate <= 0_f64 {
+            return None;
+        }
+        self.bbox.clip(r, *ray_t).map(|span| (span, rate))
+    }
+}
+
+// Samples the exponentially distributed distance, in ray parameter units, to the next
+// tentative collision.
+fn free_flight(rate: f64, rng: &mut Sampler) -> f64 {
+    -(1_f64 - rng.random_double()).ln() / rate
+}
+
+// The material of a collision inside an emissive medium: scatters like the medium's phase
+// function, and emits the light found where the collision happened.
+struct Collision {
+    phase_function: Arc<dyn Material>,
+    emitted: Color,
+}
+
+impl Material for Collision {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+        rng: &mut Sampler
+    ) -> bool {
+        self.phase_function.scatter(r_in, rec, attenuation, scattered, rng)
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        self.phase_function.scattering_pdf(r_in, rec, scattered)
+    }
+
+    fn emitted(&self, _u: f64, _v: f64, _p: &Point3) -> Color {
+        self.emitted
+    }
+}
+
+impl Hittable for HeterogeneousMedium {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        rng: &mut Sampler
+    ) -> bool {
+        let Some((span, rate)) = self.tracking_span(r, ray_t) else {
+            return false;
+        };
+
+        // Delta tracking.
+        let mut t: f64 = span.min;
+        loop {
+            t += free_flight(rate, rng);
+            if t >= span.max {
+                return false;
+            }
+            if rng.random_double() < self.density_ratio(&r.at(t)) {
+                break;
+            }
+        }
+
+        rec.t = t;
+        rec.p = r.at(t);
+
+        // Arbitrary; the phase function does not use them.
+        rec.normal = Vec3::new(1_f64, 0_f64, 0_f64);
+        rec.front_face = true;
+        (rec.u, rec.v) = (0_f64, 0_f64);
+
+        rec.mat = Some(match &self.emission {
+            // Emission is collected at collisions, in proportion to the chance that the
+            // collision is an absorption.
+            Some((field, color)) => {
+                let strength: f64 = field.density(&self.local(&rec.p));
+                let absorbed: Color = Color::new(1_f64, 1_f64, 1_f64) - self.albedo;
+                Arc::new(Collision {
+                    phase_function: Arc::clone(&self.phase_function),
+                    emitted: strength * absorbed * *color,
+                })
+            }
+            None => Arc::clone(&self.phase_function),
+        });
+
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    fn transmittance(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> f64 {
+        let Some((span, rate)) = self.tracking_span(r, ray_t) else {
+            return 1_f64;
+        };
+
+        // Ratio tracking.
+        let mut transmittance: f64 = 1_f64;
+        let mut t: f64 = span.min;
+        loop {
+            t += free_flight(rate, rng);
+            if t >= span.max {
+                return transmittance;
+            }
+            transmittance *= 1_f64 - self.density_ratio(&r.at(t));
+            if transmittance <= 0_f64 {
+                return 0_f64;
+            }
+        }
+    }
+}
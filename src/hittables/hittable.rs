@@ -1,12 +1,23 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
 use crate::geometry::vec3::{Vec3, Point3};
 use crate::geometry::ray::Ray;
 use crate::geometry::interval::Interval;
 
-#[derive(Clone, Copy)]
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+
+#[derive(Clone)]
 pub struct HitRecord {
     pub p: Point3,
     pub normal: Vec3,
+    pub mat: Option<Arc<dyn Material>>,
     pub t: f64,
+    // Surface coordinates of the hit point.
+    pub u: f64,
+    pub v: f64,
     pub front_face: bool,
 }
 
@@ -14,17 +25,23 @@ impl HitRecord {
     pub fn new(
         p: Point3, 
         normal: Vec3, 
+        mat: Option<Arc<dyn Material>>,
         t: f64, 
+        u: f64,
+        v: f64,
         front_face: bool
     ) -> Self {
-        Self { p, normal, t, front_face }
+        Self { p, normal, mat, t, u, v, front_face }
     }
 
     pub(crate) fn default() -> Self {
         Self {
             p: Point3::new(0_f64, 0_f64, 0_f64),
             normal: Vec3::new(0_f64, 0_f64, 0_f64),
+            mat: None,
             t: 0_f64,
+            u: 0_f64,
+            v: 0_f64,
             front_face: false,
         }
     }
@@ -33,20 +50,43 @@ impl HitRecord {
         // Sets the hit record normal vector.
         // NOTE: the parameter `outward_normal` is assumed to have unit length.
 
-        self.front_face = Vec3::dot(&r.direction, &outward_normal) < 0_f64;
+        self.front_face = Vec3::dot(&r.direction, outward_normal) < 0_f64;
         if self.front_face {
-            self.normal = outward_normal.clone();
+            self.normal = *outward_normal;
         } else {
-            self.normal = -1_f64 * outward_normal.clone();
+            self.normal = -*outward_normal;
         }
     }
 }
 
-pub trait Hittable {
+pub trait Hittable: Send + Sync {
     fn hit(
         &self, 
         r: &Ray, 
         ray_t: &Interval, 
-        rec: &mut HitRecord
+        rec: &mut HitRecord,
+        rng: &mut Sampler
     ) -> bool;
-}
\ No newline at end of file
+
+    fn bounding_box(&self) -> Aabb;
+
+    // Estimates the fraction of light getting through along the ray over `ray_t`, as seen
+    // by shadow rays. Surfaces block light completely; participating media let some of it
+    // through, and override this with a lower variance estimate than a hit test.
+    fn transmittance(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> f64 {
+        let mut rec: HitRecord = HitRecord::default();
+        if self.hit(r, ray_t, &mut rec, rng) { 0_f64 } else { 1_f64 }
+    }
+
+    // Density, with respect to solid angle, of `random` choosing the direction of `r` from
+    // its origin at its time. Used to sample light sources directly; objects that cannot be
+    // sampled keep the defaults, and must not be given to the renderer as lights.
+    fn pdf_value(&self, _r: &Ray) -> f64 {
+        0_f64
+    }
+
+    // Returns a direction from `origin` towards a random point of the object at `time`.
+    fn random(&self, _origin: &Point3, _time: f64, _rng: &mut Sampler) -> Vec3 {
+        Vec3::new(1_f64, 0_f64, 0_f64)
+    }
+}
@@ -1,23 +1,39 @@
 use std::sync::Arc;
 
+use crate::geometry::aabb::Aabb;
 use crate::geometry::ray::Ray;
 use crate::geometry::interval::Interval;
+use crate::geometry::vec3::{Point3, Vec3};
 
 use super::hittable::Hittable;
 use super::hittable::HitRecord;
 
+use crate::sampling::sampler::Sampler;
+
 pub struct HittableList {
     objects: Vec<Arc<dyn Hittable>>,
+    bbox: Aabb,
+}
+
+impl Default for HittableList {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HittableList {
     pub fn new() -> Self {
-        Self { objects: Vec::new() }
+        Self { objects: Vec::new(), bbox: Aabb::EMPTY }
     }
 
     pub fn add(&mut self, object: Arc<dyn Hittable>) {
+        self.bbox = Aabb::enclosing(&self.bbox, &object.bounding_box());
         self.objects.push(object);
     }
+
+    pub fn objects(&self) -> &[Arc<dyn Hittable>] {
+        &self.objects
+    }
 }
 
 impl Hittable for HittableList {
@@ -25,20 +41,53 @@ impl Hittable for HittableList {
         &self, 
         r: &Ray, 
         ray_t: &Interval, 
-        rec: &mut HitRecord
+        rec: &mut HitRecord,
+        rng: &mut Sampler
     ) -> bool {
         let mut temp_rec: HitRecord = HitRecord::default();
         let mut hit_anything: bool = false;
         let mut closest_so_far: f64 = ray_t.max;
         
         for object in self.objects.iter() {
-            if object.hit(&r, &Interval::new(ray_t.min, closest_so_far), &mut temp_rec) {
+            if object.hit(r, &Interval::new(ray_t.min, closest_so_far), &mut temp_rec, rng) {
                 hit_anything = true;
                 closest_so_far = temp_rec.t;
-                *rec = temp_rec;
+                *rec = temp_rec.clone();
             }
         }
 
         hit_anything
     }
+
+    fn transmittance(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> f64 {
+        let mut transmittance: f64 = 1_f64;
+        for object in self.objects.iter() {
+            transmittance *= object.transmittance(r, ray_t, rng);
+            if transmittance == 0_f64 {
+                break;
+            }
+        }
+        transmittance
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    // Each object is chosen with equal probability.
+    fn pdf_value(&self, r: &Ray) -> f64 {
+        if self.objects.is_empty() {
+            return 0_f64;
+        }
+        let sum: f64 = self.objects.iter().map(|object| object.pdf_value(r)).sum();
+        sum / self.objects.len() as f64
+    }
+
+    fn random(&self, origin: &Point3, time: f64, rng: &mut Sampler) -> Vec3 {
+        if self.objects.is_empty() {
+            return Vec3::new(1_f64, 0_f64, 0_f64);
+        }
+        let index: i32 = rng.random_int(0, self.objects.len() as i32 - 1);
+        self.objects[index as usize].random(origin, time, rng)
+    }
 }
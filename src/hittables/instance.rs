@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::mat4::Mat4;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::transform::{MovingTransform, Placement, Transform};
+
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+
+// One placement of shared geometry. The geometry is held by reference, so any number of
+// instances of a heavy mesh cost only a transform and an optional material each. When a
+// material is given it replaces whatever the geometry's own surfaces carry.
+pub struct Instance {
+    placed: Box<dyn Hittable>,
+    mat: Option<Arc<dyn Material>>,
+}
+
+impl Instance {
+    pub fn new(object: Arc<dyn Hittable>, matrix: Mat4) -> Self {
+        Self { placed: Box::new(Transform::new(object, matrix)), mat: None }
+    }
+
+    // An instance moving from `start` at time 0 to `end` at time 1.
+    pub fn moving(object: Arc<dyn Hittable>, start: Placement, end: Placement) -> Self {
+        Self { placed: Box::new(MovingTransform::new(object, start, end)), mat: None }
+    }
+
+    pub fn with_material(mut self, mat: Arc<dyn Material>) -> Self {
+        self.mat = Some(mat);
+        self
+    }
+}
+
+impl Hittable for Instance {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        rng: &mut Sampler
+    ) -> bool {
+        if !self.placed.hit(r, ray_t, rec, rng) {
+            return false;
+        }
+
+        if let Some(mat) = &self.mat {
+            rec.mat = Some(Arc::clone(mat));
+        }
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.placed.bounding_box()
+    }
+
+    fn transmittance(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> f64 {
+        self.placed.transmittance(r, ray_t, rng)
+    }
+
+    fn pdf_value(&self, r: &Ray) -> f64 {
+        self.placed.pdf_value(r)
+    }
+
+    fn random(&self, origin: &Point3, time: f64, rng: &mut Sampler) -> Vec3 {
+        self.placed.random(origin, time, rng)
+    }
+}
@@ -0,0 +1,165 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::triangle;
+
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+
+// Indexed triangle data. Normals and texture coordinates, when present, are indexed by the
+// same vertex indices as the positions.
+pub struct MeshData {
+    pub positions: Vec<Point3>,
+    pub normals: Option<Vec<Vec3>>,
+    pub uvs: Option<Vec<[f64; 2]>>,
+    pub indices: Vec<[usize; 3]>,
+}
+
+impl MeshData {
+    pub fn new(positions: Vec<Point3>, indices: Vec<[usize; 3]>) -> Self {
+        Self { positions, normals: None, uvs: None, indices }
+    }
+
+    pub fn with_normals(mut self, normals: Vec<Vec3>) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    pub fn with_uvs(mut self, uvs: Vec<[f64; 2]>) -> Self {
+        self.uvs = Some(uvs);
+        self
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn vertices(&self, face: usize) -> [Point3; 3] {
+        self.indices[face].map(|i| self.positions[i])
+    }
+}
+
+// Mesh data that does not describe a valid mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeshError {
+    IndexOutOfRange { index: usize, vertex_count: usize },
+    NormalCount { count: usize, vertex_count: usize },
+    UvCount { count: usize, vertex_count: usize },
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshError::IndexOutOfRange { index, vertex_count } => {
+                write!(f, "mesh index {} is out of range of {} vertices", index, vertex_count)
+            }
+            MeshError::NormalCount { count, vertex_count } => {
+                write!(f, "mesh has {} normals for {} vertices", count, vertex_count)
+            }
+            MeshError::UvCount { count, vertex_count } => {
+                write!(f, "mesh has {} texture coordinates for {} vertices", count, vertex_count)
+            }
+        }
+    }
+}
+
+impl Error for MeshError {}
+
+// A triangle mesh sharing one vertex buffer among all of its faces, with an internal BVH
+// over the faces. The buffers are reference counted, so meshes built from the same
+// `MeshData` do not duplicate it.
+pub struct Mesh {
+    data: Arc<MeshData>,
+    bvh: BvhNode,
+}
+
+impl Mesh {
+    pub fn new(data: Arc<MeshData>, mat: Arc<dyn Material>) -> Result<Self, MeshError> {
+        let vertex_count: usize = data.positions.len();
+        if let Some(&index) = data.indices.iter().flatten().find(|&&i| i >= vertex_count) {
+            return Err(MeshError::IndexOutOfRange { index, vertex_count });
+        }
+        if let Some(normals) = data.normals.as_ref().filter(|normals| normals.len() != vertex_count) {
+            return Err(MeshError::NormalCount { count: normals.len(), vertex_count });
+        }
+        if let Some(uvs) = data.uvs.as_ref().filter(|uvs| uvs.len() != vertex_count) {
+            return Err(MeshError::UvCount { count: uvs.len(), vertex_count });
+        }
+
+        let faces: Vec<Arc<dyn Hittable>> = (0..data.triangle_count())
+            .map(|face| {
+                let face: Arc<dyn Hittable> = Arc::new(MeshTriangle {
+                    data: Arc::clone(&data),
+                    face,
+                    mat: Arc::clone(&mat),
+                });
+                face
+            })
+            .collect();
+
+        Ok(Self { data, bvh: BvhNode::from_objects(faces) })
+    }
+
+    pub fn data(&self) -> &Arc<MeshData> {
+        &self.data
+    }
+}
+
+impl Hittable for Mesh {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        rng: &mut Sampler
+    ) -> bool {
+        self.bvh.hit(r, ray_t, rec, rng)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bvh.bounding_box()
+    }
+}
+
+// One face of a mesh, referring back to the shared vertex buffers.
+struct MeshTriangle {
+    data: Arc<MeshData>,
+    face: usize,
+    mat: Arc<dyn Material>,
+}
+
+impl Hittable for MeshTriangle {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        _rng: &mut Sampler
+    ) -> bool {
+        let vertices: [Point3; 3] = self.data.vertices(self.face);
+        let Some((t, b1, b2)) = triangle::intersect(&vertices, r, ray_t) else {
+            return false;
+        };
+
+        let index: [usize; 3] = self.data.indices[self.face];
+        let normals: Option<[Vec3; 3]> = self.data.normals.as_ref().map(|n| index.map(|i| n[i]));
+        let uvs: Option<[[f64; 2]; 3]> = self.data.uvs.as_ref().map(|uv| index.map(|i| uv[i]));
+
+        rec.mat = Some(Arc::clone(&self.mat));
+        triangle::fill_hit_record(rec, r, t, [b1, b2], &vertices, normals.as_ref(), uvs.as_ref());
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        triangle::bounding_box(&self.data.vertices(self.face))
+    }
+}
@@ -1,3 +1,13 @@
+pub mod bvh;
+pub mod constant_medium;
+pub mod disk;
+pub mod heterogeneous_medium;
 pub mod hittable_list;
 pub mod hittable;
+pub mod instance;
+pub mod mesh;
+pub mod plane;
+pub mod quad;
 pub mod sphere;
+pub mod transform;
+pub mod triangle;
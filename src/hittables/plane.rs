@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::onb::Onb;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+
+// An infinite plane through `point`, facing along `normal`. Its (u, v) surface coordinates
+// are distances from `point` along two tangent directions, so they are unbounded and in
+// world units. The bounding box is infinite along the plane.
+pub struct Plane {
+    pub point: Point3,
+    pub normal: Vec3,
+    pub mat: Arc<dyn Material>,
+    frame: Onb,
+    bbox: Aabb,
+}
+
+impl Plane {
+    pub fn new(point: Point3, normal: Vec3, mat: Arc<dyn Material>) -> Self {
+        let frame = Onb::new(&normal);
+        let normal: Vec3 = frame.w;
+
+        // Only an axis the plane is perpendicular to has finite bounds.
+        let axis = |i: usize| -> Interval {
+            if normal[(i + 1) % 3] == 0_f64 && normal[(i + 2) % 3] == 0_f64 {
+                Interval::new(point[i], point[i])
+            } else {
+                Interval::UNIVERSE
+            }
+        };
+        let bbox: Aabb = Aabb::new(axis(0), axis(1), axis(2));
+
+        Self { point, normal, mat, frame, bbox }
+    }
+}
+
+impl Hittable for Plane {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        _rng: &mut Sampler
+    ) -> bool {
+        let denom: f64 = Vec3::dot(&self.normal, &r.direction);
+        if denom.abs() < 1e-8 {
+            return false;
+        }
+
+        let t: f64 = Vec3::dot(&self.normal, &(self.point - r.origin)) / denom;
+        if !ray_t.surrounds(t) {
+            return false;
+        }
+
+        rec.t = t;
+        rec.p = r.at(t);
+        let offset: Vec3 = rec.p - self.point;
+        rec.u = Vec3::dot(&offset, &self.frame.u);
+        rec.v = Vec3::dot(&offset, &self.frame.v);
+        rec.mat = Some(Arc::clone(&self.mat));
+        rec.set_face_normal(r, &self.normal);
+
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::hittable_list::HittableList;
+
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+
+// A parallelogram spanned by the edges `u` and `v` from the corner `q`. The front face is
+// the side that `u × v` points towards, and (u, v) surface coordinates run from 0 to 1
+// along the two edges.
+pub struct Quad {
+    pub q: Point3,
+    pub u: Vec3,
+    pub v: Vec3,
+    pub mat: Arc<dyn Material>,
+    // Plane equation `normal · p = d`, and the vector used to find the planar coordinates
+    // of a point.
+    normal: Vec3,
+    d: f64,
+    w: Vec3,
+    area: f64,
+    bbox: Aabb,
+}
+
+impl Quad {
+    pub fn new(q: Point3, u: Vec3, v: Vec3, mat: Arc<dyn Material>) -> Self {
+        let n: Vec3 = Vec3::cross(&u, &v);
+        let normal: Vec3 = Vec3::unit_vector(n);
+        let d: f64 = Vec3::dot(&normal, &q);
+        let w: Vec3 = n / Vec3::dot(&n, &n);
+
+        // Enclose both diagonals of the parallelogram.
+        let bbox: Aabb = Aabb::enclosing(
+            &Aabb::from_points(q, q + u + v),
+            &Aabb::from_points(q + u, q + v),
+        );
+
+        Self { q, u, v, mat, normal, d, w, area: n.length(), bbox }
+    }
+
+    // Returns the ray parameter and the planar coordinates of the point where the ray
+    // crosses the parallelogram within `ray_t`, if it does.
+    fn intersect(&self, r: &Ray, ray_t: &Interval) -> Option<(f64, f64, f64)> {
+        let denom: f64 = Vec3::dot(&self.normal, &r.direction);
+
+        // No hit if the ray is parallel to the plane.
+        if denom.abs() < 1e-8 {
+            return None;
+        }
+
+        let t: f64 = (self.d - Vec3::dot(&self.normal, &r.origin)) / denom;
+        if !ray_t.surrounds(t) {
+            return None;
+        }
+
+        // Determine if the hit point lies within the parallelogram, using its planar coordinates.
+        let planar_hitpt: Vec3 = r.at(t) - self.q;
+        let alpha: f64 = Vec3::dot(&self.w, &Vec3::cross(&planar_hitpt, &self.v));
+        let beta: f64 = Vec3::dot(&self.w, &Vec3::cross(&self.u, &planar_hitpt));
+
+        let unit: Interval = Interval::new(0_f64, 1_f64);
+        if !unit.contains(alpha) || !unit.contains(beta) {
+            return None;
+        }
+        Some((t, alpha, beta))
+    }
+}
+
+impl Hittable for Quad {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        _rng: &mut Sampler
+    ) -> bool {
+        let Some((t, alpha, beta)) = self.intersect(r, ray_t) else {
+            return false;
+        };
+
+        rec.t = t;
+        rec.p = r.at(t);
+        rec.u = alpha;
+        rec.v = beta;
+        rec.mat = Some(Arc::clone(&self.mat));
+        rec.set_face_normal(r, &self.normal);
+
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    // Points are uniform over the area, which makes the density over directions grow with
+    // the squared distance to the point, and with how obliquely the quad is seen there.
+    fn pdf_value(&self, r: &Ray) -> f64 {
+        let Some((t, _, _)) = self.intersect(r, &Interval::new(0.001, f64::INFINITY)) else {
+            return 0_f64;
+        };
+
+        let distance_squared: f64 = t * t * r.direction.length_squared();
+        let cosine: f64 = Vec3::dot(&r.direction, &self.normal).abs() / r.direction.length();
+        distance_squared / (cosine * self.area)
+    }
+
+    fn random(&self, origin: &Point3, _time: f64, rng: &mut Sampler) -> Vec3 {
+        let p: Point3 = self.q + (rng.random_double() * self.u) + (rng.random_double() * self.v);
+        p - *origin
+    }
+}
+
+// Returns the box with the opposite vertices `a` and `b`, built from six quads facing
+// outwards.
+pub fn make_box(a: Point3, b: Point3, mat: Arc<dyn Material>) -> HittableList {
+    let mut sides = HittableList::new();
+
+    let min = Point3::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z()));
+    let max = Point3::new(a.x().max(b.x()), a.y().max(b.y()), a.z().max(b.z()));
+
+    let dx = Vec3::new(max.x() - min.x(), 0_f64, 0_f64);
+    let dy = Vec3::new(0_f64, max.y() - min.y(), 0_f64);
+    let dz = Vec3::new(0_f64, 0_f64, max.z() - min.z());
+
+    let faces: [(Point3, Vec3, Vec3); 6] = [
+        (Point3::new(min.x(), min.y(), max.z()), dx, dy), // front
+        (Point3::new(max.x(), min.y(), max.z()), -dz, dy), // right
+        (Point3::new(max.x(), min.y(), min.z()), -dx, dy), // back
+        (Point3::new(min.x(), min.y(), min.z()), dz, dy), // left
+        (Point3::new(min.x(), max.y(), max.z()), dx, -dz), // top
+        (Point3::new(min.x(), min.y(), min.z()), dx, dz), // bottom
+    ];
+    for (q, u, v) in faces {
+        sides.add(Arc::new(Quad::new(q, u, v, Arc::clone(&mat))));
+    }
+
+    sides
+}
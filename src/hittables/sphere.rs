@@ -1,17 +1,81 @@
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
 use crate::geometry::vec3::{Vec3, Point3};
 use crate::geometry::ray::Ray;
 use crate::geometry::interval::Interval;
+use crate::geometry::onb::Onb;
 
 use crate::hittables::hittable::{Hittable, HitRecord};
 
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+use crate::sampling::warp;
+
 pub struct Sphere {
+    // Center at time 0, and its displacement from there to time 1. Outside that interval
+    // the sphere rests at the nearer end of its path.
     pub center: Point3,
+    pub motion: Vec3,
     pub radius: f64,
+    pub mat: Arc<dyn Material>,
+    bbox: Aabb,
 }
 
 impl Sphere {
-    pub fn new(center: Point3, radius: f64) -> Self {
-        Self { center, radius }
+    pub fn new(center: Point3, radius: f64, mat: Arc<dyn Material>) -> Self {
+        Self::moving(center, center, radius, mat)
+    }
+
+    // A sphere moving in a straight line from `center0` at time 0 to `center1` at time 1.
+    // The radius must not be negative.
+    pub fn moving(center0: Point3, center1: Point3, radius: f64, mat: Arc<dyn Material>) -> Self {
+        assert!(radius >= 0_f64, "sphere radius must not be negative, got {}", radius);
+        let rvec: Vec3 = Vec3::new(radius, radius, radius);
+        let bbox: Aabb = Aabb::enclosing(
+            &Aabb::from_points(center0 - rvec, center0 + rvec),
+            &Aabb::from_points(center1 - rvec, center1 + rvec),
+        );
+
+        Self { center: center0, motion: center1 - center0, radius, mat, bbox }
+    }
+
+    pub fn center_at(&self, time: f64) -> Point3 {
+        self.center + time.clamp(0_f64, 1_f64) * self.motion
+    }
+
+    fn get_sphere_uv(p: &Point3) -> (f64, f64) {
+        // p: a given point on the sphere of radius one, centered at the origin.
+        // u: returned value [0,1] of angle around the Y axis from X=-1.
+        // v: returned value [0,1] of angle from Y=-1 to Y=+1.
+        //     <1 0 0> yields <0.50 0.50>       <-1  0  0> yields <0.00 0.50>
+        //     <0 1 0> yields <0.50 1.00>       < 0 -1  0> yields <0.50 0.00>
+        //     <0 0 1> yields <0.25 0.50>       < 0  0 -1> yields <0.75 0.50>
+
+        let theta: f64 = f64::acos((-p.y()).clamp(-1_f64, 1_f64));
+        let phi: f64 = f64::atan2(-p.z(), p.x()) + PI;
+
+        (phi / (2_f64 * PI), theta / PI)
+    }
+
+    // Cosine of the half angle of the cone subtended by the sphere, seen from `to_center`
+    // away from its center, or None from inside the sphere.
+    fn cos_theta_max(&self, to_center: &Vec3) -> Option<f64> {
+        let distance_squared: f64 = to_center.length_squared();
+        let radius_squared: f64 = self.radius * self.radius;
+        if distance_squared <= radius_squared {
+            return None;
+        }
+        Some((1_f64 - radius_squared / distance_squared).sqrt())
+    }
+
+    // 1 - cos_theta_max, computed without cancellation so that distant spheres, subtending
+    // tiny cones, keep an accurate solid angle.
+    fn cone_height(&self, to_center: &Vec3, cos_theta_max: f64) -> f64 {
+        let sin_squared: f64 = self.radius * self.radius / to_center.length_squared();
+        sin_squared / (1_f64 + cos_theta_max)
     }
 }
 
@@ -20,9 +84,11 @@ impl Hittable for Sphere {
         &self, 
         r: &Ray, 
         ray_t: &Interval,
-        rec: &mut HitRecord
+        rec: &mut HitRecord,
+        _rng: &mut Sampler
     ) -> bool {
-        let oc: Vec3 = self.center - r.origin;
+        let center: Point3 = self.center_at(r.time);
+        let oc: Vec3 = center - r.origin;
 
         let a: f64 = r.direction.length_squared();
         let h: f64 = Vec3::dot(&r.direction, &oc);
@@ -44,9 +110,39 @@ impl Hittable for Sphere {
         rec.t = root;
         rec.p = r.at(rec.t);
 
-        let outward_normal: Vec3 = (rec.p - self.center) / self.radius;
+        let outward_normal: Vec3 = (rec.p - center) / self.radius;
         rec.set_face_normal(r, &outward_normal);
+        (rec.u, rec.v) = Self::get_sphere_uv(&outward_normal);
+        rec.mat = Some(Arc::clone(&self.mat));
 
         true
     }
-}
\ No newline at end of file
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    // Seen from outside, directions are uniform over the cone the sphere subtends. From
+    // inside, every direction reaches the sphere, and directions are uniform over all of them.
+    fn pdf_value(&self, r: &Ray) -> f64 {
+        let to_center: Vec3 = self.center_at(r.time) - r.origin;
+        let Some(cos_theta_max) = self.cos_theta_max(&to_center) else {
+            return 1_f64 / (4_f64 * PI);
+        };
+
+        let cos_angle: f64 = Vec3::dot(&Vec3::unit_vector(r.direction), &Vec3::unit_vector(to_center));
+        if cos_angle < cos_theta_max {
+            return 0_f64;
+        }
+        1_f64 / (2_f64 * PI * self.cone_height(&to_center, cos_theta_max))
+    }
+
+    fn random(&self, origin: &Point3, time: f64, rng: &mut Sampler) -> Vec3 {
+        let to_center: Vec3 = self.center_at(time) - *origin;
+        let Some(cos_theta_max) = self.cos_theta_max(&to_center) else {
+            return Vec3::random_unit_vector(rng);
+        };
+
+        Onb::new(&to_center).transform(&warp::square_to_uniform_cone(rng.random_2d(), cos_theta_max))
+    }
+}
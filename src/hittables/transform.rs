@@ -0,0 +1,292 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::mat3::Mat3;
+use crate::geometry::mat4::Mat4;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+
+use crate::sampling::sampler::Sampler;
+
+// Places an object in the world through an affine transform. Rays are carried into the
+// object's own space by the inverse matrix, and hits are carried back out: points by the
+// matrix, and normals by the inverse transpose of its linear part, so that they stay
+// perpendicular to surfaces under non-uniform scaling. Ray directions are not renormalized,
+// so the ray parameter `t` is the same in both spaces.
+pub struct Transform {
+    object: Arc<dyn Hittable>,
+    matrix: Mat4,
+    inverse: Mat4,
+    normal_matrix: Mat3,
+    bbox: Aabb,
+}
+
+impl Transform {
+    pub fn new(object: Arc<dyn Hittable>, matrix: Mat4) -> Self {
+        assert!(matrix.m[3] == [0_f64, 0_f64, 0_f64, 1_f64], "transform matrix is not affine");
+        let inverse: Mat4 = matrix.inverse().expect("transform matrix is singular");
+        let normal_matrix: Mat3 = inverse.linear().transpose();
+        let bbox: Aabb = transform_box(&object.bounding_box(), &matrix);
+
+        Self { object, matrix, inverse, normal_matrix, bbox }
+    }
+
+    pub fn translate(object: Arc<dyn Hittable>, offset: Vec3) -> Self {
+        Self::new(object, Mat4::translation(offset))
+    }
+
+    // Rotation by `degrees` about the y axis, through the origin.
+    pub fn rotate_y(object: Arc<dyn Hittable>, degrees: f64) -> Self {
+        Self::new(object, Mat4::rotation_y(degrees))
+    }
+
+    pub fn scale(object: Arc<dyn Hittable>, factors: Vec3) -> Self {
+        Self::new(object, Mat4::scaling(factors))
+    }
+
+    pub fn matrix(&self) -> &Mat4 {
+        &self.matrix
+    }
+}
+
+impl Hittable for Transform {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        rng: &mut Sampler
+    ) -> bool {
+        // Transform the ray from world space to object space.
+        let object_ray: Ray = to_object_space(r, &self.inverse);
+
+        // Determine whether an intersection exists in object space (and if so, where).
+        if !self.object.hit(&object_ray, ray_t, rec, rng) {
+            return false;
+        }
+
+        // Transform the intersection from object space back to world space. The normal
+        // already faces against the ray, and keeps doing so under the transform.
+        rec.p = self.matrix.transform_point(&rec.p);
+        rec.normal = Vec3::unit_vector(self.normal_matrix * rec.normal);
+
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    fn transmittance(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> f64 {
+        self.object.transmittance(&to_object_space(r, &self.inverse), ray_t, rng)
+    }
+
+    fn pdf_value(&self, r: &Ray) -> f64 {
+        let object_pdf: f64 = self.object.pdf_value(&to_object_space(r, &self.inverse));
+        to_world_pdf(object_pdf, &r.direction, &self.inverse)
+    }
+
+    fn random(&self, origin: &Point3, time: f64, rng: &mut Sampler) -> Vec3 {
+        let object_origin: Point3 = self.inverse.transform_point(origin);
+        self.matrix.transform_vector(&self.object.random(&object_origin, time, rng))
+    }
+}
+
+// An affine transform written as its parts: a scale, rotations about the x, y and z axes
+// (in degrees, applied in that order), and a translation, applied in that order.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Placement {
+    pub scale: Vec3,
+    pub rotate: Vec3,
+    pub translate: Vec3,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self {
+            scale: Vec3::new(1_f64, 1_f64, 1_f64),
+            rotate: Vec3::default(),
+            translate: Vec3::default(),
+        }
+    }
+}
+
+impl Placement {
+    pub fn matrix(&self) -> Mat4 {
+        Mat4::translation(self.translate)
+            * Mat4::rotation_z(self.rotate.z())
+            * Mat4::rotation_y(self.rotate.y())
+            * Mat4::rotation_x(self.rotate.x())
+            * Mat4::scaling(self.scale)
+    }
+
+    // The inverse of `matrix`, built from the inverted parts in reverse order rather than
+    // by a general matrix inversion.
+    pub fn inverse_matrix(&self) -> Mat4 {
+        let s: Vec3 = self.scale;
+        Mat4::scaling(Vec3::new(1_f64 / s.x(), 1_f64 / s.y(), 1_f64 / s.z()))
+            * Mat4::rotation_x(-self.rotate.x())
+            * Mat4::rotation_y(-self.rotate.y())
+            * Mat4::rotation_z(-self.rotate.z())
+            * Mat4::translation(-self.translate)
+    }
+
+    // Interpolates each part linearly, from `self` at 0 to `other` at 1.
+    pub fn lerp(&self, other: &Placement, t: f64) -> Placement {
+        Placement {
+            scale: self.scale + t * (other.scale - self.scale),
+            rotate: self.rotate + t * (other.rotate - self.rotate),
+            translate: self.translate + t * (other.translate - self.translate),
+        }
+    }
+}
+
+// Number of instants at which the path of a moving transform is sampled to bound it.
+const MOTION_STEPS: usize = 32;
+
+// A transform animated from one placement at time 0 to another at time 1, with each part
+// interpolated linearly in between; outside that interval the object rests at the nearer
+// end. The placement is evaluated for every ray at the ray's time.
+pub struct MovingTransform {
+    object: Arc<dyn Hittable>,
+    start: Placement,
+    end: Placement,
+    bbox: Aabb,
+}
+
+impl MovingTransform {
+    pub fn new(object: Arc<dyn Hittable>, start: Placement, end: Placement) -> Self {
+        // A scale factor changing sign would pass through zero on the way.
+        let nonsingular = |i: usize| start.scale[i] * end.scale[i] > 0_f64;
+        assert!((0..3).all(nonsingular), "moving transform scale reaches zero");
+
+        let bbox: Aabb = sweep_box(&object.bounding_box(), &start, &end);
+        Self { object, start, end, bbox }
+    }
+
+    pub fn placement_at(&self, time: f64) -> Placement {
+        self.start.lerp(&self.end, time.clamp(0_f64, 1_f64))
+    }
+}
+
+impl Hittable for MovingTransform {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        rng: &mut Sampler
+    ) -> bool {
+        let placement: Placement = self.placement_at(r.time);
+        let matrix: Mat4 = placement.matrix();
+        let inverse: Mat4 = placement.inverse_matrix();
+
+        let object_ray: Ray = to_object_space(r, &inverse);
+        if !self.object.hit(&object_ray, ray_t, rec, rng) {
+            return false;
+        }
+
+        rec.p = matrix.transform_point(&rec.p);
+        rec.normal = Vec3::unit_vector(inverse.linear().transpose() * rec.normal);
+
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    fn transmittance(&self, r: &Ray, ray_t: &Interval, rng: &mut Sampler) -> f64 {
+        let inverse: Mat4 = self.placement_at(r.time).inverse_matrix();
+        self.object.transmittance(&to_object_space(r, &inverse), ray_t, rng)
+    }
+
+    fn pdf_value(&self, r: &Ray) -> f64 {
+        let inverse: Mat4 = self.placement_at(r.time).inverse_matrix();
+        let object_pdf: f64 = self.object.pdf_value(&to_object_space(r, &inverse));
+        to_world_pdf(object_pdf, &r.direction, &inverse)
+    }
+
+    fn random(&self, origin: &Point3, time: f64, rng: &mut Sampler) -> Vec3 {
+        let placement: Placement = self.placement_at(time);
+        let object_origin: Point3 = placement.inverse_matrix().transform_point(origin);
+        placement.matrix().transform_vector(&self.object.random(&object_origin, time, rng))
+    }
+}
+
+// Carries a world space ray into object space. The direction is not renormalized, so
+// distances along the ray keep their parameter `t`.
+fn to_object_space(r: &Ray, inverse: &Mat4) -> Ray {
+    Ray::with_time(inverse.transform_point(&r.origin), inverse.transform_vector(&r.direction), r.time)
+}
+
+// Converts `object_pdf`, a density over object space directions, into the density over
+// world space directions at `direction`. Unless the transform's linear part `L` is a
+// rotation or a uniform scaling, it stretches solid angles: a small solid angle around the
+// unit world space direction `ω` maps to one `|det L⁻¹| / |L⁻¹ ω|³` times its size around
+// the object space direction `L⁻¹ ω`.
+fn to_world_pdf(object_pdf: f64, direction: &Vec3, inverse: &Mat4) -> f64 {
+    let linear: Mat3 = inverse.linear();
+    let stretch: f64 = (linear * Vec3::unit_vector(*direction)).length();
+    object_pdf * linear.determinant().abs() / stretch.powi(3)
+}
+
+// Returns the box enclosing the eight transformed corners of `bbox`. Unbounded boxes stay
+// unbounded, as their corners cannot be transformed.
+fn transform_box(bbox: &Aabb, matrix: &Mat4) -> Aabb {
+    if !bbox.is_bounded() {
+        return Aabb::UNIVERSE;
+    }
+
+    let mut result: Aabb = Aabb::EMPTY;
+    for corner in 0..8 {
+        let p = Point3::new(
+            if corner & 1 == 0 { bbox.x.min } else { bbox.x.max },
+            if corner & 2 == 0 { bbox.y.min } else { bbox.y.max },
+            if corner & 4 == 0 { bbox.z.min } else { bbox.z.max },
+        );
+        let q: Point3 = matrix.transform_point(&p);
+        result = Aabb::enclosing(&result, &Aabb::from_points(q, q));
+    }
+    result
+}
+
+// Bounds the box swept by `bbox` as it moves from `start` to `end`. The box is placed at
+// evenly spaced instants, and the union of those placements is padded by the farthest any
+// corner travels between two consecutive instants, which covers the curved paths that
+// rotations take in between.
+fn sweep_box(bbox: &Aabb, start: &Placement, end: &Placement) -> Aabb {
+    if !bbox.is_bounded() {
+        return Aabb::UNIVERSE;
+    }
+
+    let corners: Vec<Point3> = (0..8)
+        .map(|corner| Point3::new(
+            if corner & 1 == 0 { bbox.x.min } else { bbox.x.max },
+            if corner & 2 == 0 { bbox.y.min } else { bbox.y.max },
+            if corner & 4 == 0 { bbox.z.min } else { bbox.z.max },
+        ))
+        .collect();
+
+    let mut result: Aabb = Aabb::EMPTY;
+    let mut previous: Option<Vec<Point3>> = None;
+    let mut step: f64 = 0_f64;
+    for i in 0..=MOTION_STEPS {
+        let matrix: Mat4 = start.lerp(end, i as f64 / MOTION_STEPS as f64).matrix();
+        let placed: Vec<Point3> = corners.iter().map(|p| matrix.transform_point(p)).collect();
+        for q in placed.iter() {
+            result = Aabb::enclosing(&result, &Aabb::from_points(*q, *q));
+        }
+        if let Some(previous) = &previous {
+            for (a, b) in previous.iter().zip(placed.iter()) {
+                step = step.max((*b - *a).length());
+            }
+        }
+        previous = Some(placed);
+    }
+
+    Aabb::new(result.x.expand(2_f64 * step), result.y.expand(2_f64 * step), result.z.expand(2_f64 * step))
+}
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::interval::Interval;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+
+use crate::materials::material::Material;
+
+use crate::sampling::sampler::Sampler;
+use crate::sampling::warp;
+
+pub struct Triangle {
+    pub vertices: [Point3; 3],
+    // Optional per-vertex shading normals and texture coordinates.
+    pub normals: Option<[Vec3; 3]>,
+    pub uvs: Option<[[f64; 2]; 3]>,
+    pub mat: Arc<dyn Material>,
+    bbox: Aabb,
+}
+
+impl Triangle {
+    pub fn new(a: Point3, b: Point3, c: Point3, mat: Arc<dyn Material>) -> Self {
+        Self {
+            vertices: [a, b, c],
+            normals: None,
+            uvs: None,
+            mat,
+            bbox: bounding_box(&[a, b, c]),
+        }
+    }
+
+    pub fn with_normals(mut self, normals: [Vec3; 3]) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    pub fn with_uvs(mut self, uvs: [[f64; 2]; 3]) -> Self {
+        self.uvs = Some(uvs);
+        self
+    }
+}
+
+impl Hittable for Triangle {
+    fn hit(
+        &self,
+        r: &Ray,
+        ray_t: &Interval,
+        rec: &mut HitRecord,
+        _rng: &mut Sampler
+    ) -> bool {
+        let Some((t, b1, b2)) = intersect(&self.vertices, r, ray_t) else {
+            return false;
+        };
+
+        rec.mat = Some(Arc::clone(&self.mat));
+        fill_hit_record(rec, r, t, [b1, b2], &self.vertices, self.normals.as_ref(), self.uvs.as_ref());
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    // Points are uniform over the area, as for `Quad`.
+    fn pdf_value(&self, r: &Ray) -> f64 {
+        let Some((t, _, _)) = intersect(&self.vertices, r, &Interval::new(0.001, f64::INFINITY)) else {
+            return 0_f64;
+        };
+
+        let [a, b, c] = self.vertices;
+        let n: Vec3 = Vec3::cross(&(b - a), &(c - a));
+        let distance_squared: f64 = t * t * r.direction.length_squared();
+        let cosine: f64 = Vec3::dot(&r.direction, &n).abs() / (r.direction.length() * n.length());
+        distance_squared / (cosine * 0.5 * n.length())
+    }
+
+    fn random(&self, origin: &Point3, _time: f64, rng: &mut Sampler) -> Vec3 {
+        let (b1, b2) = warp::square_to_uniform_triangle(rng.random_2d());
+        let [a, b, c] = self.vertices;
+        a + b1 * (b - a) + b2 * (c - a) - *origin
+    }
+}
+
+pub(crate) fn bounding_box(vertices: &[Point3; 3]) -> Aabb {
+    Aabb::enclosing(
+        &Aabb::from_points(vertices[0], vertices[1]),
+        &Aabb::from_points(vertices[2], vertices[2]),
+    )
+}
+
+// Möller–Trumbore ray/triangle intersection. On a hit, returns the ray parameter and the
+// barycentric coordinates (b1, b2) of the hit point with respect to vertices 1 and 2.
+pub(crate) fn intersect(vertices: &[Point3; 3], r: &Ray, ray_t: &Interval) -> Option<(f64, f64, f64)> {
+    let edge1: Vec3 = vertices[1] - vertices[0];
+    let edge2: Vec3 = vertices[2] - vertices[0];
+
+    let pvec: Vec3 = Vec3::cross(&r.direction, &edge2);
+    let det: f64 = Vec3::dot(&edge1, &pvec);
+
+    // The ray is parallel to the triangle's plane.
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv_det: f64 = 1_f64 / det;
+
+    let tvec: Vec3 = r.origin - vertices[0];
+    let b1: f64 = Vec3::dot(&tvec, &pvec) * inv_det;
+    if !(0_f64..=1_f64).contains(&b1) {
+        return None;
+    }
+
+    let qvec: Vec3 = Vec3::cross(&tvec, &edge1);
+    let b2: f64 = Vec3::dot(&r.direction, &qvec) * inv_det;
+    if b2 < 0_f64 || b1 + b2 > 1_f64 {
+        return None;
+    }
+
+    let t: f64 = Vec3::dot(&edge2, &qvec) * inv_det;
+    if !ray_t.surrounds(t) {
+        return None;
+    }
+
+    Some((t, b1, b2))
+}
+
+// Sets the geometric fields of a triangle hit. The face orientation always comes from the
+// geometric normal; interpolated vertex normals, when present, only bend the shading normal
+// and are flipped onto the same side as the geometric one.
+// Without vertex texture coordinates, (u, v) are the barycentric coordinates.
+pub(crate) fn fill_hit_record(
+    rec: &mut HitRecord,
+    r: &Ray,
+    t: f64,
+    barycentric: [f64; 2],
+    vertices: &[Point3; 3],
+    normals: Option<&[Vec3; 3]>,
+    uvs: Option<&[[f64; 2]; 3]>,
+) {
+    let [b1, b2] = barycentric;
+    let b0: f64 = 1_f64 - b1 - b2;
+
+    rec.t = t;
+    rec.p = r.at(t);
+
+    let geometric: Vec3 = Vec3::unit_vector(Vec3::cross(&(vertices[1] - vertices[0]), &(vertices[2] - vertices[0])));
+    rec.set_face_normal(r, &geometric);
+
+    if let Some(n) = normals {
+        let shading: Vec3 = Vec3::unit_vector(b0 * n[0] + b1 * n[1] + b2 * n[2]);
+        rec.normal = if Vec3::dot(&shading, &rec.normal) < 0_f64 { -shading } else { shading };
+    }
+
+    match uvs {
+        Some(uv) => {
+            rec.u = b0 * uv[0][0] + b1 * uv[1][0] + b2 * uv[2][0];
+            rec.v = b0 * uv[0][1] + b1 * uv[1][1] + b2 * uv[2][1];
+        }
+        None => {
+            rec.u = b1;
+            rec.v = b2;
+        }
+    }
+}
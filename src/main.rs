@@ -1,83 +1,101 @@
-use image::{ImageBuffer, Rgb};
-use rand::Rng;
-
 use std::env;
-use std::path::Path;
-use std::sync::Arc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::f64::consts::PI;
 
 pub mod camera;
+pub mod cli;
+pub mod environment;
 pub mod geometry;
 pub mod hittables;
+pub mod materials;
+pub mod output;
+pub mod sampling;
+pub mod scene;
 pub mod test;
+pub mod textures;
+pub mod volumes;
 
 use crate::camera::camera::Camera;
-use crate::geometry::vec3::Point3;
 
-use crate::hittables::sphere::Sphere;
-use crate::hittables::hittable_list::HittableList;
+use crate::cli::{Command, Options};
+
+use crate::hittables::bvh::BvhNode;
+
+use crate::output::framebuffer::Framebuffer;
+use crate::output::writer;
+
+use crate::scene::error::SceneError;
+use crate::scene::loader::{self, Scene};
 
 fn degrees_to_radians(degrees: f64) -> f64 {
     degrees * PI / 180_f64
 }
 
-fn random_double() -> f64 {
-    let mut rng = rand::thread_rng();
-    rng.gen_range(0.0..=1.0)
-}
-
-fn random_double_range(min: f64, max: f64) -> f64 {
-    let mut rng = rand::thread_rng();
-    rng.gen_range(min..=max)
-}
+// Scene rendered when no scene file is given.
+const DEFAULT_SCENE: &str = include_str!("../scenes/three_spheres.scene");
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+fn main() -> ExitCode {
+    let options: Options = match cli::parse(env::args().skip(1)) {
+        Ok(Command::Render(options)) => options,
+        Ok(Command::Help) => {
+            print!("{}", cli::USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {}\n\nRun with --help for usage.", err);
+            return ExitCode::from(2);
+        }
+    };
 
-    let mut resolution: u32 = 512;
-    if let Some(arg) = args.get(1) {
-        match arg.parse::<u32>() {
-            Ok(parsed_res) => resolution = parsed_res,
-            Err(_) => {
-                eprintln!("Invalid resolution provided, using default: {}", resolution);
-            }
+    // world; the seed also drives the noise generated while the scene loads.
+    let scene: Result<Scene, SceneError> = match &options.scene {
+        Some(scene_path) => loader::load_file(scene_path, options.seed),
+        None => loader::parse(DEFAULT_SCENE, options.seed),
+    };
+    let scene: Scene = match scene {
+        Ok(scene) => scene,
+        Err(err) => {
+            let name = options.scene.as_deref().map_or("<default>".into(), Path::to_string_lossy);
+            eprintln!("error: failed to load scene {}: {}", name, err);
+            return ExitCode::FAILURE;
         }
-    }
+    };
 
-    let mut camera_samples: u32 = 100;
-    if let Some(arg) = args.get(2) {
-        match arg.parse::<u32>() {
-            Ok(parsed_sample_rate) => camera_samples = parsed_sample_rate,
-            Err(_) => {
-                eprintln!("Invalid sample rate provided, using default: {}", camera_samples);
-            }
+    // render
+    let camera: Camera = match options.apply_to_camera(scene.camera.lights(scene.lights)).build() {
+        Ok(camera) => camera,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
         }
-    }
+    };
+    let world: BvhNode = BvhNode::new(scene.world);
+    let framebuffer: Framebuffer = camera.render(&world);
 
-    // world
-    let mut world: HittableList = HittableList::new();
-    world.add(Arc::new(Sphere::new(
-        Point3::new(0_f64, 0_f64, -1_f64),
-        0.5,
-    )));
-    world.add(Arc::new(Sphere::new(
-        Point3::new(0_f64, -100.5, -1_f64),
-        100_f64,
-    )));
+    let path: PathBuf = match &options.output {
+        Some(path) => path.clone(),
+        None => PathBuf::from(format!(
+            "out/{:.prec$}_{1}_{2}.png", 
+            camera.aspect_ratio(), 
+            camera.image_width(), 
+            camera.samples_per_pixel(),
+            prec = 2,
+        )),
+    };
 
-    // render
-    let aspect_ratio: f64 = 16_f64 / 9_f64;
-    let camera: Camera = Camera::new(aspect_ratio, resolution, camera_samples);
-    let img: ImageBuffer<Rgb<u16>, Vec<u16>> = camera.render(&world);
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        if let Err(err) = fs::create_dir_all(parent) {
+            eprintln!("error: failed to create {}: {}", parent.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
 
-    let img_name = format!(
-        "out/{:.prec$}_{1}_{2}.png", 
-        aspect_ratio, 
-        resolution, 
-        camera_samples,
-        prec = 2,
-    );
-    let path = Path::new(&img_name);
+    if let Err(err) = writer::save(&framebuffer, &path, &options.apply_to_output(scene.output)) {
+        eprintln!("error: failed to write {}: {}", path.display(), err);
+        return ExitCode::FAILURE;
+    }
 
-    let _ = img.save(path).unwrap_or(());
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,62 @@
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Color, Vec3};
+
+use crate::hittables::hittable::HitRecord;
+
+use crate::sampling::sampler::Sampler;
+
+use super::material::Material;
+
+pub struct Dielectric {
+    // Refractive index in vacuum or air, or the ratio of the material's refractive index over
+    // the refractive index of the enclosing media.
+    pub refraction_index: f64,
+}
+
+impl Dielectric {
+    pub fn new(refraction_index: f64) -> Self {
+        Self { refraction_index }
+    }
+
+    pub fn reflectance(cosine: f64, refraction_index: f64) -> f64 {
+        // Use Schlick's approximation for reflectance.
+        let r0: f64 = (1_f64 - refraction_index) / (1_f64 + refraction_index);
+        let r0: f64 = r0 * r0;
+
+        r0 + (1_f64 - r0) * (1_f64 - cosine).powi(5)
+    }
+}
+
+impl Material for Dielectric {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+        rng: &mut Sampler
+    ) -> bool {
+        *attenuation = Color::new(1_f64, 1_f64, 1_f64);
+        let ri: f64 = if rec.front_face {
+            1_f64 / self.refraction_index
+        } else {
+            self.refraction_index
+        };
+
+        let unit_direction: Vec3 = Vec3::unit_vector(r_in.direction);
+        let cos_theta: f64 = Vec3::dot(&-unit_direction, &rec.normal).min(1_f64);
+        let sin_theta: f64 = (1_f64 - cos_theta * cos_theta).sqrt();
+
+        // Total internal reflection, or a Fresnel reflection chosen by chance.
+        let cannot_refract: bool = ri * sin_theta > 1_f64;
+        let direction: Vec3 = if cannot_refract || Self::reflectance(cos_theta, ri) > rng.random_double() {
+            Vec3::reflect(&unit_direction, &rec.normal)
+        } else {
+            Vec3::refract(&unit_direction, &rec.normal, ri)
+        };
+
+        *scattered = Ray::with_time(rec.p, direction, r_in.time);
+
+        true
+    }
+}
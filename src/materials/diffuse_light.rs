@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Color, Point3};
+
+use crate::hittables::hittable::HitRecord;
+
+use crate::sampling::sampler::Sampler;
+
+use crate::textures::solid_color::SolidColor;
+use crate::textures::texture::Texture;
+
+use super::material::Material;
+
+// A light source: emits its texture's color from both sides and absorbs every incoming ray.
+// Emission is radiance, so values above one are expected for lights that illuminate a scene.
+pub struct DiffuseLight {
+    pub tex: Arc<dyn Texture>,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: Color) -> Self {
+        Self::from_texture(Arc::new(SolidColor::new(emit)))
+    }
+
+    pub fn from_texture(tex: Arc<dyn Texture>) -> Self {
+        Self { tex }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(
+        &self,
+        _r_in: &Ray,
+        _rec: &HitRecord,
+        _attenuation: &mut Color,
+        _scattered: &mut Ray,
+        _rng: &mut Sampler
+    ) -> bool {
+        false
+    }
+
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        self.tex.value(u, v, p)
+    }
+}
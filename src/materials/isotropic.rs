@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::Color;
+
+use crate::hittables::hittable::HitRecord;
+
+use crate::sampling::pdf::{Pdf, SpherePdf};
+use crate::sampling::sampler::Sampler;
+
+use crate::textures::solid_color::SolidColor;
+use crate::textures::texture::Texture;
+
+use super::material::Material;
+
+// The phase function of a participating medium: scatters light equally in every direction,
+// regardless of where it came from. Hits inside a medium have no meaningful normal, so it
+// is ignored.
+pub struct Isotropic {
+    pub tex: Arc<dyn Texture>,
+}
+
+impl Isotropic {
+    pub fn new(albedo: Color) -> Self {
+        Self::from_texture(Arc::new(SolidColor::new(albedo)))
+    }
+
+    pub fn from_texture(tex: Arc<dyn Texture>) -> Self {
+        Self { tex }
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+        rng: &mut Sampler
+    ) -> bool {
+        *scattered = Ray::with_time(rec.p, SpherePdf.generate(rng), r_in.time);
+        *attenuation = self.tex.value(rec.u, rec.v, &rec.p);
+
+        true
+    }
+
+    fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, scattered: &Ray) -> f64 {
+        SpherePdf.value(&scattered.direction)
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Color, Vec3};
+
+use crate::hittables::hittable::HitRecord;
+
+use crate::sampling::pdf::{CosinePdf, Pdf};
+use crate::sampling::sampler::Sampler;
+
+use crate::textures::solid_color::SolidColor;
+use crate::textures::texture::Texture;
+
+use super::material::Material;
+
+pub struct Lambertian {
+    pub tex: Arc<dyn Texture>,
+}
+
+impl Lambertian {
+    pub fn new(albedo: Color) -> Self {
+        Self::from_texture(Arc::new(SolidColor::new(albedo)))
+    }
+
+    pub fn from_texture(tex: Arc<dyn Texture>) -> Self {
+        Self { tex }
+    }
+}
+
+impl Material for Lambertian {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+        rng: &mut Sampler
+    ) -> bool {
+        let scatter_direction: Vec3 = CosinePdf::new(&rec.normal).generate(rng);
+
+        *scattered = Ray::with_time(rec.p, scatter_direction, r_in.time);
+        *attenuation = self.tex.value(rec.u, rec.v, &rec.p);
+
+        true
+    }
+
+    fn scattering_pdf(&self, _r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        CosinePdf::new(&rec.normal).value(&scattered.direction)
+    }
+}
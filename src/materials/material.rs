@@ -0,0 +1,34 @@
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Color, Point3};
+
+use crate::hittables::hittable::HitRecord;
+
+use crate::sampling::sampler::Sampler;
+
+pub trait Material: Send + Sync {
+    // Returns true if the incoming ray is scattered, in which case `attenuation`
+    // and `scattered` are set. Returns false if the ray is absorbed.
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+        rng: &mut Sampler
+    ) -> bool;
+
+    // Density, with respect to solid angle, with which `scatter` picks the direction of
+    // `scattered`. Together with `attenuation` it gives the light scattered towards any
+    // direction, which is `attenuation` times this density, so that lights can be sampled
+    // directly. Materials that scatter into a single direction, such as mirrors and glass,
+    // return zero, and lights are not sampled for them.
+    fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f64 {
+        0_f64
+    }
+
+    // Returns the light given off at surface coordinates (u, v) and point `p`. Materials
+    // that are not light sources emit nothing.
+    fn emitted(&self, _u: f64, _v: f64, _p: &Point3) -> Color {
+        Color::default()
+    }
+}
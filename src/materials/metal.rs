@@ -0,0 +1,39 @@
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Color, Vec3};
+
+use crate::hittables::hittable::HitRecord;
+
+use crate::sampling::sampler::Sampler;
+
+use super::material::Material;
+
+pub struct Metal {
+    pub albedo: Color,
+    pub fuzz: f64,
+}
+
+impl Metal {
+    pub fn new(albedo: Color, fuzz: f64) -> Self {
+        Self { albedo, fuzz: fuzz.clamp(0_f64, 1_f64) }
+    }
+}
+
+impl Material for Metal {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+        rng: &mut Sampler
+    ) -> bool {
+        let reflected: Vec3 = Vec3::reflect(&r_in.direction, &rec.normal);
+        let reflected: Vec3 = Vec3::unit_vector(reflected) + (self.fuzz * Vec3::random_unit_vector(rng));
+
+        *scattered = Ray::with_time(rec.p, reflected, r_in.time);
+        *attenuation = self.albedo;
+
+        // Fuzzed rays that end up below the surface are absorbed.
+        Vec3::dot(&scattered.direction, &rec.normal) > 0_f64
+    }
+}
@@ -0,0 +1,6 @@
+pub mod material;
+pub mod lambertian;
+pub mod metal;
+pub mod dielectric;
+pub mod diffuse_light;
+pub mod isotropic;
@@ -0,0 +1,77 @@
+use image::{ImageBuffer, Rgb, Rgb32FImage};
+
+use crate::geometry::interval::Interval;
+use crate::geometry::vec3::Color;
+
+use super::transform::OutputTransform;
+
+// A floating-point image holding the renderer's linear, unbounded radiance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Framebuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color>,
+}
+
+impl Framebuffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Color::default(); width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Color {
+        self.pixels[self.index(x, y)]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, color: Color) {
+        let index = self.index(x, y);
+        self.pixels[index] = color;
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.width && y < self.height, "pixel ({}, {}) out of bounds", x, y);
+        y as usize * self.width as usize + x as usize
+    }
+
+    // Linear radiance as 32-bit floats, for HDR formats.
+    pub fn to_rgb32f(&self) -> Rgb32FImage {
+        ImageBuffer::from_fn(self.width, self.height, |x, y| {
+            let c: Color = self.get(x, y);
+            Rgb([c.x() as f32, c.y() as f32, c.z() as f32])
+        })
+    }
+
+    // Display-encoded 16-bit image.
+    pub fn to_rgb16(&self, transform: &OutputTransform) -> ImageBuffer<Rgb<u16>, Vec<u16>> {
+        ImageBuffer::from_fn(self.width, self.height, |x, y| {
+            Rgb(Self::quantize(transform.apply(self.get(x, y)), u16::MAX as f64).map(|c| c as u16))
+        })
+    }
+
+    // Display-encoded 8-bit image.
+    pub fn to_rgb8(&self, transform: &OutputTransform) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        ImageBuffer::from_fn(self.width, self.height, |x, y| {
+            Rgb(Self::quantize(transform.apply(self.get(x, y)), u8::MAX as f64).map(|c| c as u8))
+        })
+    }
+
+    fn quantize(color: Color, max: f64) -> [f64; 3] {
+        let intensity = Interval::new(0_f64, 0.999);
+        [
+            (max + 1_f64) * intensity.clamp(color.x()),
+            (max + 1_f64) * intensity.clamp(color.y()),
+            (max + 1_f64) * intensity.clamp(color.z()),
+        ]
+    }
+}
@@ -0,0 +1,3 @@
+pub mod framebuffer;
+pub mod transform;
+pub mod writer;
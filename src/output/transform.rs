@@ -0,0 +1,105 @@
+use crate::geometry::vec3::Color;
+
+// Compresses unbounded scene radiance into [0,1] before encoding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneMap {
+    // Values are passed through and clipped at 1.
+    None,
+    // x / (1 + x), applied per channel.
+    Reinhard,
+    // Narkowicz's fit of the ACES filmic reference rendering transform.
+    AcesFilmic,
+}
+
+// Encodes display-linear values for storage in an 8 or 16 bit image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferFunction {
+    Linear,
+    // x^(1/gamma), e.g. `Gamma(2.0)`.
+    Gamma(f64),
+    // The piecewise sRGB opto-electronic transfer function (IEC 61966-2-1).
+    Srgb,
+}
+
+// The stage applied to each pixel between the renderer's linear radiance and quantization:
+// exposure, then tone mapping, then the transfer function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputTransform {
+    // Exposure adjustment in stops; the radiance is scaled by 2^exposure.
+    pub exposure: f64,
+    pub tone_map: ToneMap,
+    pub transfer: TransferFunction,
+}
+
+impl Default for OutputTransform {
+    fn default() -> Self {
+        Self {
+            exposure: 0_f64,
+            tone_map: ToneMap::None,
+            transfer: TransferFunction::Srgb,
+        }
+    }
+}
+
+impl OutputTransform {
+    pub fn new(exposure: f64, tone_map: ToneMap, transfer: TransferFunction) -> Self {
+        Self { exposure, tone_map, transfer }
+    }
+
+    // Maps a linear radiance value to an encoded value in [0,1].
+    pub fn apply(&self, color: Color) -> Color {
+        let scale: f64 = self.exposure.exp2();
+        let mut out: Color = Color::default();
+        for i in 0..3 {
+            let x: f64 = self.tone_map.apply(scale * color[i]);
+            out[i] = self.transfer.encode(x.clamp(0_f64, 1_f64));
+        }
+        out
+    }
+}
+
+impl ToneMap {
+    pub fn apply(&self, x: f64) -> f64 {
+        // Negative or NaN radiance can only come from numerical trouble; treat it as black.
+        let x: f64 = if x > 0_f64 { x } else { 0_f64 };
+        match self {
+            ToneMap::None => x,
+            ToneMap::Reinhard => x / (1_f64 + x),
+            ToneMap::AcesFilmic => {
+                let (a, b, c, d, e) = (2.51, 0.03, 2.43, 0.59, 0.14);
+                (x * (a * x + b)) / (x * (c * x + d) + e)
+            }
+        }
+    }
+}
+
+impl TransferFunction {
+    pub fn encode(&self, x: f64) -> f64 {
+        match *self {
+            TransferFunction::Linear => x,
+            TransferFunction::Gamma(gamma) => x.powf(1_f64 / gamma),
+            TransferFunction::Srgb => {
+                if x <= 0.003_130_8 {
+                    12.92 * x
+                } else {
+                    1.055 * x.powf(1_f64 / 2.4) - 0.055
+                }
+            }
+        }
+    }
+
+    // The inverse of `encode`, for reading encoded images back as linear values.
+    pub fn decode(&self, x: f64) -> f64 {
+        match *self {
+            TransferFunction::Linear => x,
+            TransferFunction::Gamma(gamma) => x.powf(gamma),
+            TransferFunction::Srgb => {
+                if x <= 0.040_45 {
+                    x / 12.92
+                } else {
+                    ((x + 0.055) / 1.055).powf(2.4)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use image::codecs::hdr::HdrEncoder;
+use image::error::{ImageFormatHint, UnsupportedError, UnsupportedErrorKind};
+use image::{ImageError, ImageFormat, ImageResult};
+
+use super::framebuffer::Framebuffer;
+use super::transform::OutputTransform;
+
+// Writes the framebuffer to `path`, choosing the format from the file extension. HDR formats
+// (`.exr`, `.hdr`) store the linear radiance unchanged; all other formats are passed through
+// `transform` and quantized.
+pub fn save(framebuffer: &Framebuffer, path: &Path, transform: &OutputTransform) -> ImageResult<()> {
+    let format = ImageFormat::from_path(path)?;
+    match format {
+        ImageFormat::OpenExr => framebuffer.to_rgb32f().save_with_format(path, format),
+        ImageFormat::Hdr => {
+            // `image` only exposes Radiance HDR output through the encoder itself.
+            let img = framebuffer.to_rgb32f();
+            let pixels: Vec<_> = img.pixels().copied().collect();
+            let writer = BufWriter::new(File::create(path).map_err(ImageError::IoError)?);
+            HdrEncoder::new(writer).encode(&pixels, img.width() as usize, img.height() as usize)
+        }
+        ImageFormat::Png | ImageFormat::Tiff => framebuffer.to_rgb16(transform).save_with_format(path, format),
+        ImageFormat::Jpeg | ImageFormat::Pnm | ImageFormat::Bmp | ImageFormat::Tga => {
+            framebuffer.to_rgb8(transform).save_with_format(path, format)
+        }
+        _ => Err(ImageError::Unsupported(UnsupportedError::from_format_and_kind(
+            ImageFormatHint::Exact(format),
+            UnsupportedErrorKind::Format(ImageFormatHint::Exact(format)),
+        ))),
+    }
+}
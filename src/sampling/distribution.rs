@@ -0,0 +1,102 @@
+// Piecewise-constant probability distributions, for drawing samples in proportion to a
+// tabulated function such as the brightness of an image.
+
+// A distribution over [0,1), split into equal cells with densities proportional to the
+// given non-negative weights.
+pub struct Distribution1D {
+    weights: Vec<f64>,
+    cdf: Vec<f64>,
+    total: f64,
+}
+
+impl Distribution1D {
+    pub fn new(weights: Vec<f64>) -> Self {
+        assert!(!weights.is_empty(), "distribution needs at least one cell");
+
+        let mut cdf: Vec<f64> = Vec::with_capacity(weights.len() + 1);
+        cdf.push(0_f64);
+        let mut total: f64 = 0_f64;
+        for &weight in weights.iter() {
+            total += weight.max(0_f64);
+            cdf.push(total);
+        }
+
+        // With nothing to prefer, fall back to a uniform distribution.
+        let n: f64 = weights.len() as f64;
+        if total > 0_f64 {
+            cdf.iter_mut().for_each(|c| *c /= total);
+        } else {
+            cdf.iter_mut().enumerate().for_each(|(i, c)| *c = i as f64 / n);
+        }
+
+        Self { weights, cdf, total }
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.weights.len()
+    }
+
+    // Sum of the weights.
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    // Maps a uniform `xi` in [0,1) to a point in [0,1), returning it with the index of the
+    // cell it lies in.
+    pub fn sample(&self, xi: f64) -> (f64, usize) {
+        // The last cell whose cdf starts at or below xi, skipping empty cells.
+        let cell: usize = (self.cdf.partition_point(|&c| c <= xi) - 1).min(self.cell_count() - 1);
+        let (lo, hi) = (self.cdf[cell], self.cdf[cell + 1]);
+        let offset: f64 = if hi > lo { (xi - lo) / (hi - lo) } else { 0.5 };
+
+        let x: f64 = (cell as f64 + offset) / self.cell_count() as f64;
+        (x.min(1_f64 - f64::EPSILON), cell)
+    }
+
+    // Probability of choosing `cell`.
+    pub fn probability(&self, cell: usize) -> f64 {
+        self.cdf[cell + 1] - self.cdf[cell]
+    }
+
+    // Density at any point of `cell`, with respect to [0,1).
+    pub fn pdf(&self, cell: usize) -> f64 {
+        self.probability(cell) * self.cell_count() as f64
+    }
+}
+
+// A distribution over [0,1)², split into a grid of `width` by `height` cells given in
+// row-major order. A row is chosen from the marginal distribution, then a column from
+// that row's conditional one.
+pub struct Distribution2D {
+    rows: Vec<Distribution1D>,
+    marginal: Distribution1D,
+}
+
+impl Distribution2D {
+    pub fn new(weights: &[f64], width: usize, height: usize) -> Self {
+        assert_eq!(weights.len(), width * height, "weight count does not match grid size");
+
+        let rows: Vec<Distribution1D> = weights
+            .chunks(width)
+            .map(|row| Distribution1D::new(row.to_vec()))
+            .collect();
+        let marginal = Distribution1D::new(rows.iter().map(Distribution1D::total).collect());
+
+        Self { rows, marginal }
+    }
+
+    // Maps two uniform numbers to a point (x, y) in [0,1)², returning it with its density.
+    pub fn sample(&self, xi: [f64; 2]) -> ([f64; 2], f64) {
+        let (y, row) = self.marginal.sample(xi[1]);
+        let (x, column) = self.rows[row].sample(xi[0]);
+        ([x, y], self.marginal.pdf(row) * self.rows[row].pdf(column))
+    }
+
+    // Density at the point (x, y) in [0,1)².
+    pub fn pdf(&self, point: [f64; 2]) -> f64 {
+        let row: usize = ((point[1] * self.rows.len() as f64) as usize).min(self.rows.len() - 1);
+        let columns: usize = self.rows[row].cell_count();
+        let column: usize = ((point[0] * columns as f64) as usize).min(columns - 1);
+        self.marginal.pdf(row) * self.rows[row].pdf(column)
+    }
+}
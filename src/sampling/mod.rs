@@ -0,0 +1,4 @@
+pub mod distribution;
+pub mod pdf;
+pub mod sampler;
+pub mod warp;
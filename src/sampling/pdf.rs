@@ -0,0 +1,144 @@
+// Probability densities over directions, with respect to solid angle, for importance
+// sampling: each can both draw a direction and tell the density with which it would have
+// drawn any given one.
+
+use crate::environment::environment::Environment;
+
+use crate::geometry::onb::Onb;
+use crate::geometry::ray::Ray;
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::hittable::Hittable;
+
+use super::sampler::Sampler;
+use super::warp;
+
+pub trait Pdf {
+    // Density of `generate` choosing `direction`, which need not have unit length.
+    fn value(&self, direction: &Vec3) -> f64;
+
+    fn generate(&self, rng: &mut Sampler) -> Vec3;
+}
+
+// Uniform over all directions.
+pub struct SpherePdf;
+
+impl Pdf for SpherePdf {
+    fn value(&self, _direction: &Vec3) -> f64 {
+        warp::uniform_sphere_pdf()
+    }
+
+    fn generate(&self, rng: &mut Sampler) -> Vec3 {
+        Vec3::random_unit_vector(rng)
+    }
+}
+
+// Proportional to the cosine of the angle to `normal`, over the hemisphere around it; the
+// distribution of light scattered by a lambertian surface.
+pub struct CosinePdf {
+    frame: Onb,
+}
+
+impl CosinePdf {
+    pub fn new(normal: &Vec3) -> Self {
+        Self { frame: Onb::new(normal) }
+    }
+}
+
+impl Pdf for CosinePdf {
+    fn value(&self, direction: &Vec3) -> f64 {
+        warp::cosine_hemisphere_pdf(Vec3::dot(&Vec3::unit_vector(*direction), &self.frame.w))
+    }
+
+    fn generate(&self, rng: &mut Sampler) -> Vec3 {
+        self.frame.transform(&warp::square_to_cosine_hemisphere(rng.random_2d()))
+    }
+}
+
+// Towards the surfaces of `objects`, as seen from `origin` at `time`; used to aim rays at
+// light sources. See `Hittable::pdf_value`.
+pub struct HittablePdf<'a> {
+    objects: &'a dyn Hittable,
+    origin: Point3,
+    time: f64,
+}
+
+impl<'a> HittablePdf<'a> {
+    pub fn new(objects: &'a dyn Hittable, origin: Point3, time: f64) -> Self {
+        Self { objects, origin, time }
+    }
+}
+
+impl Pdf for HittablePdf<'_> {
+    fn value(&self, direction: &Vec3) -> f64 {
+        self.objects.pdf_value(&Ray::with_time(self.origin, *direction, self.time))
+    }
+
+    fn generate(&self, rng: &mut Sampler) -> Vec3 {
+        self.objects.random(&self.origin, self.time, rng)
+    }
+}
+
+// Towards the bright parts of an environment.
+pub struct EnvironmentPdf<'a> {
+    environment: &'a dyn Environment,
+}
+
+impl<'a> EnvironmentPdf<'a> {
+    pub fn new(environment: &'a dyn Environment) -> Self {
+        Self { environment }
+    }
+}
+
+impl Pdf for EnvironmentPdf<'_> {
+    fn value(&self, direction: &Vec3) -> f64 {
+        self.environment.pdf_value(direction)
+    }
+
+    fn generate(&self, rng: &mut Sampler) -> Vec3 {
+        self.environment.random(rng)
+    }
+}
+
+// Draws from `first` with probability `weight`, and from `second` otherwise.
+pub struct MixturePdf<A: Pdf, B: Pdf> {
+    first: A,
+    second: B,
+    weight: f64,
+}
+
+impl<A: Pdf, B: Pdf> MixturePdf<A, B> {
+    pub fn new(first: A, second: B, weight: f64) -> Self {
+        Self { first, second, weight: weight.clamp(0_f64, 1_f64) }
+    }
+}
+
+impl<A: Pdf, B: Pdf> Pdf for MixturePdf<A, B> {
+    fn value(&self, direction: &Vec3) -> f64 {
+        // Components that are never drawn from are not evaluated.
+        let mut value: f64 = 0_f64;
+        if self.weight > 0_f64 {
+            value += self.weight * self.first.value(direction);
+        }
+        if self.weight < 1_f64 {
+            value += (1_f64 - self.weight) * self.second.value(direction);
+        }
+        value
+    }
+
+    fn generate(&self, rng: &mut Sampler) -> Vec3 {
+        if rng.random_double() < self.weight {
+            self.first.generate(rng)
+        } else {
+            self.second.generate(rng)
+        }
+    }
+}
+
+// Weight of a sample drawn with density `pdf` when the same direction could also have been
+// drawn by a second strategy with density `other`. The weights of the two strategies sum to
+// one, and favor whichever was more likely to find the direction.
+pub fn power_heuristic(pdf: f64, other: f64) -> f64 {
+    let (a, b) = (pdf * pdf, other * other);
+    if a + b > 0_f64 { a / (a + b) } else { 0_f64 }
+}
@@ -0,0 +1,58 @@
+// Source of random numbers for the renderer. Every random decision made while tracing a
+// path draws from the sampler passed down the call chain, so a render is a pure function
+// of its seed. The generator is SplitMix64, implemented here rather than taken from `rand`
+// so that its streams never change with the crate version or the target.
+pub struct Sampler {
+    state: u64,
+}
+
+impl Sampler {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    // Returns the independent stream for pixel i, j of a render seeded with `seed`.
+    pub fn for_pixel(seed: u64, i: u32, j: u32) -> Self {
+        let pixel: u64 = ((j as u64) << 32) | i as u64;
+        Self::new(mix(seed ^ mix(pixel)))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let z: u64 = mix(self.state);
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        z
+    }
+
+    // Returns a random real in [0,1), from the top 53 bits of the next output.
+    pub fn random_double(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1_f64 / (1_u64 << 53) as f64)
+    }
+
+    // Returns a random real in [min,max).
+    pub fn random_double_range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.random_double()
+    }
+
+    // Returns a random point in [0,1)², for the warps of `warp`.
+    pub fn random_2d(&mut self) -> [f64; 2] {
+        [self.random_double(), self.random_double()]
+    }
+
+    // Returns a random integer in [min,max], by scaling the next output to the range.
+    pub fn random_int(&mut self, min: i32, max: i32) -> i32 {
+        let range: u64 = (max as i64 - min as i64 + 1) as u64;
+        let offset: u64 = ((self.next_u64() as u128 * range as u128) >> 64) as u64;
+        (min as i64 + offset as i64) as i32
+    }
+}
+
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// SplitMix64 finalizer: the generator's output function, also used to decorrelate nearby
+// seeds.
+fn mix(x: u64) -> u64 {
+    let mut z: u64 = x.wrapping_add(GOLDEN_GAMMA);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
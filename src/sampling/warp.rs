@@ -0,0 +1,99 @@
+// Warps from the unit square onto other domains, for turning a 2D sample point into a
+// point or direction with a known density. Each takes a point `u` in [0,1)², and maps
+// evenly spread points to evenly spread results, so that stratified or low-discrepancy
+// points keep their advantage. None of them rejects samples.
+//
+// Directions are in a local frame with `z` up; `Onb::transform` orients them around a
+// normal. Densities of directions are with respect to solid angle, and of points with
+// respect to area.
+
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use crate::geometry::vec3::Vec3;
+
+// Uniform over the unit disk in the xy plane, by taking the square root of the radius.
+// Simple, but it squeezes the square unevenly.
+pub fn square_to_uniform_disk(u: [f64; 2]) -> Vec3 {
+    let r: f64 = u[0].sqrt();
+    let phi: f64 = 2_f64 * PI * u[1];
+    Vec3::new(r * phi.cos(), r * phi.sin(), 0_f64)
+}
+
+// Uniform over the unit disk in the xy plane, by Shirley and Chiu's concentric mapping,
+// which takes squares around the center to circles and so distorts the least.
+pub fn square_to_concentric_disk(u: [f64; 2]) -> Vec3 {
+    let a: f64 = 2_f64 * u[0] - 1_f64;
+    let b: f64 = 2_f64 * u[1] - 1_f64;
+    if a == 0_f64 && b == 0_f64 {
+        return Vec3::default();
+    }
+
+    let (r, phi) = if a.abs() > b.abs() {
+        (a, FRAC_PI_4 * (b / a))
+    } else {
+        (b, FRAC_PI_2 - FRAC_PI_4 * (a / b))
+    };
+    Vec3::new(r * phi.cos(), r * phi.sin(), 0_f64)
+}
+
+pub fn uniform_disk_pdf() -> f64 {
+    1_f64 / PI
+}
+
+pub fn square_to_uniform_sphere(u: [f64; 2]) -> Vec3 {
+    let z: f64 = 1_f64 - 2_f64 * u[0];
+    let r: f64 = (1_f64 - z * z).max(0_f64).sqrt();
+    let phi: f64 = 2_f64 * PI * u[1];
+    Vec3::new(r * phi.cos(), r * phi.sin(), z)
+}
+
+pub fn uniform_sphere_pdf() -> f64 {
+    1_f64 / (4_f64 * PI)
+}
+
+pub fn square_to_uniform_hemisphere(u: [f64; 2]) -> Vec3 {
+    let z: f64 = u[0];
+    let r: f64 = (1_f64 - z * z).max(0_f64).sqrt();
+    let phi: f64 = 2_f64 * PI * u[1];
+    Vec3::new(r * phi.cos(), r * phi.sin(), z)
+}
+
+pub fn uniform_hemisphere_pdf() -> f64 {
+    1_f64 / (2_f64 * PI)
+}
+
+// Proportional to the cosine of the angle to `z`, by projecting the concentric disk up onto
+// the hemisphere (Malley's method).
+pub fn square_to_cosine_hemisphere(u: [f64; 2]) -> Vec3 {
+    let d: Vec3 = square_to_concentric_disk(u);
+    let z: f64 = (1_f64 - d.x() * d.x() - d.y() * d.y()).max(0_f64).sqrt();
+    Vec3::new(d.x(), d.y(), z)
+}
+
+// Density of `square_to_cosine_hemisphere` at a direction making an angle with cosine
+// `cos_theta` to `z`.
+pub fn cosine_hemisphere_pdf(cos_theta: f64) -> f64 {
+    cos_theta.max(0_f64) / PI
+}
+
+// Uniform over the directions within the angle with cosine `cos_theta_max` of `z`.
+pub fn square_to_uniform_cone(u: [f64; 2], cos_theta_max: f64) -> Vec3 {
+    let z: f64 = 1_f64 - u[0] * (1_f64 - cos_theta_max);
+    let r: f64 = (1_f64 - z * z).max(0_f64).sqrt();
+    let phi: f64 = 2_f64 * PI * u[1];
+    Vec3::new(r * phi.cos(), r * phi.sin(), z)
+}
+
+pub fn uniform_cone_pdf(cos_theta_max: f64) -> f64 {
+    1_f64 / (2_f64 * PI * (1_f64 - cos_theta_max))
+}
+
+// Uniform over a triangle, as the barycentric coordinates (b1, b2) of the point with
+// respect to its second and third vertices. The square is folded along its diagonal.
+pub fn square_to_uniform_triangle(u: [f64; 2]) -> (f64, f64) {
+    if u[0] + u[1] > 1_f64 {
+        (1_f64 - u[0], 1_f64 - u[1])
+    } else {
+        (u[0], u[1])
+    }
+}
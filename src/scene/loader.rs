@@ -0,0 +1,1069 @@
+// Loader for the text scene format.
+//
+// A scene file is a sequence of directives, one per line. Each line starts with a keyword,
+// followed by positional arguments and then `key=value` properties in any order. Vectors
+// are written as comma separated triples, and `#` starts a comment:
+//
+//     render width=400 aspect=16:9 samples=100 depth=50
+//     camera from=-2,2,1 at=0,0,-1 vfov=20 shutter=0,1
+//     environment sky elevation=30 azimuth=120 turbidity=3
+//     fog density=0.02 albedo=0.9,0.9,0.9
+//     texture checks checker scale=0.5 even=0.2,0.3,0.1 odd=0.9,0.9,0.9
+//     material ground lambertian texture=checks
+//     plane point=0,-0.5,0 normal=0,1,0 material=ground
+//     mesh file=models/teapot.obj material=ground scale=0.5 rotate=0,90,0 translate=0,1,0
+//     model tree file=models/tree.obj
+//     instance tree material=ground translate=3,0,-2
+//     volume noise min=-1,1,-1 max=1,2,1 density=4 albedo=0.9,0.9,0.9
+//
+// Textures, materials and models are declared with a name before the lines that use them.
+// Files referenced by a scene are found relative to the scene file.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::camera::builder::CameraBuilder;
+use crate::camera::error::CameraError;
+
+use crate::environment::constant::ConstantEnvironment;
+use crate::environment::environment::Environment;
+use crate::environment::gradient::GradientEnvironment;
+use crate::environment::hdri::HdriEnvironment;
+use crate::environment::sun_sky::SunSkyEnvironment;
+
+use crate::geometry::mat4::Mat4;
+use crate::geometry::vec3::{Color, Vec3};
+
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::constant_medium::ConstantMedium;
+use crate::hittables::disk::Disk;
+use crate::hittables::heterogeneous_medium::HeterogeneousMedium;
+use crate::hittables::hittable::Hittable;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::instance::Instance;
+use crate::hittables::mesh::Mesh;
+use crate::hittables::plane::Plane;
+use crate::hittables::quad::{self, Quad};
+use crate::hittables::sphere::Sphere;
+use crate::hittables::transform::{MovingTransform, Placement, Transform};
+use crate::hittables::triangle::Triangle;
+
+use crate::materials::dielectric::Dielectric;
+use crate::materials::diffuse_light::DiffuseLight;
+use crate::materials::isotropic::Isotropic;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::material::Material;
+use crate::materials::metal::Metal;
+
+use crate::output::transform::{OutputTransform, ToneMap, TransferFunction};
+
+use crate::sampling::sampler::Sampler;
+
+use crate::textures::checker::{CheckerTexture, UvCheckerTexture};
+use crate::textures::image_texture::ImageTexture;
+use crate::textures::noise::{MarbleTexture, NoiseTexture, WoodTexture};
+use crate::textures::solid_color::SolidColor;
+use crate::textures::texture::Texture;
+
+use crate::volumes::density::DensityField;
+use crate::volumes::noise_density::NoiseDensity;
+use crate::volumes::voxel_grid::{RawFormat, VoxelGrid};
+
+use super::error::SceneError;
+use super::obj::{self, ObjModel};
+
+pub struct Scene {
+    pub world: HittableList,
+    // The spheres, quads, boxes, triangles and disks of the world made of a light material,
+    // for the camera to sample directly.
+    pub lights: HittableList,
+    pub camera: CameraBuilder,
+    pub output: OutputTransform,
+}
+
+// Loads a scene file. A `seed` replaces the scene's render seed, for both the camera and
+// the noise generated while loading.
+pub fn load_file(path: &Path, seed: Option<u64>) -> Result<Scene, SceneError> {
+    let source: String = fs::read_to_string(path)?;
+    parse_in(&source, path.parent().unwrap_or(Path::new("")), seed)
+}
+
+pub fn parse(source: &str, seed: Option<u64>) -> Result<Scene, SceneError> {
+    parse_in(source, Path::new(""), seed)
+}
+
+fn parse_in(source: &str, dir: &Path, seed: Option<u64>) -> Result<Scene, SceneError> {
+    // Noise is generated as the scene is read, from the render seed, so that is looked up
+    // first, wherever the `render` line is.
+    let mut render_seed: u64 = 0;
+    for (number, text) in lines(source) {
+        let mut line = Line::new(number, text)?;
+        if line.keyword == "render" {
+            if let Some(value) = line.parse("seed")? {
+                render_seed = value;
+            }
+        }
+    }
+
+    let mut loader = Loader::new(dir, seed.unwrap_or(render_seed));
+    for (number, text) in lines(source) {
+        let mut line = Line::new(number, text)?;
+        loader.directive(&mut line)?;
+        line.finish()?;
+    }
+    if let Some(seed) = seed {
+        loader.update_camera(|camera| camera.seed(seed));
+    }
+
+    Ok(loader.scene)
+}
+
+// The numbered lines of a scene that are not empty, without their comments.
+fn lines(source: &str) -> impl Iterator<Item = (usize, &str)> {
+    source.lines().enumerate().filter_map(|(index, raw)| {
+        let text: &str = match raw.find('#') {
+            Some(comment) => &raw[..comment],
+            None => raw,
+        };
+        (!text.trim().is_empty()).then_some((index + 1, text))
+    })
+}
+
+// Parses an aspect ratio written either as a number or as `width:height`.
+pub fn parse_aspect_ratio(value: &str) -> Option<f64> {
+    let parsed: Option<f64> = match value.split_once(':') {
+        Some((w, h)) => match (w.parse::<f64>(), h.parse::<f64>()) {
+            (Ok(w), Ok(h)) if h > 0_f64 => Some(w / h),
+            _ => None,
+        },
+        None => value.parse().ok(),
+    };
+    parsed.filter(|aspect| aspect.is_finite() && *aspect > 0_f64)
+}
+
+struct Loader {
+    scene: Scene,
+    textures: HashMap<String, Arc<dyn Texture>>,
+    materials: HashMap<String, Arc<dyn Material>>,
+    light_materials: Vec<Arc<dyn Material>>,
+    models: HashMap<String, Arc<dyn Hittable>>,
+    dir: PathBuf,
+    // The render seed, or zero without one; noise lattices are derived from it.
+    seed: u64,
+}
+
+impl Loader {
+    fn new(dir: &Path, seed: u64) -> Self {
+        Self {
+            scene: Scene {
+                world: HittableList::new(),
+                lights: HittableList::new(),
+                camera: CameraBuilder::new(),
+                output: OutputTransform::default(),
+            },
+            textures: HashMap::new(),
+            materials: HashMap::new(),
+            light_materials: Vec::new(),
+            models: HashMap::new(),
+            dir: dir.to_path_buf(),
+            seed,
+        }
+    }
+
+    fn directive(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        match line.keyword {
+            "render" => self.render(line),
+            "output" => self.output(line),
+            "camera" => self.camera(line),
+            "background" => self.background(line),
+            "environment" => self.environment(line),
+            "fog" => self.fog(line),
+            "texture" => self.texture(line),
+            "material" => self.material(line),
+            "sphere" => self.sphere(line),
+            "triangle" => self.triangle(line),
+            "quad" => self.quad(line),
+            "disk" => self.disk(line),
+            "plane" => self.plane(line),
+            "box" => self.cuboid(line),
+            "mesh" => self.mesh(line),
+            "volume" => self.volume(line),
+            "model" => self.model(line),
+            "instance" => self.instance(line),
+            keyword => Err(line.error(format!("unknown directive '{}'", keyword))),
+        }
+    }
+
+    fn update_camera(&mut self, f: impl FnOnce(CameraBuilder) -> CameraBuilder) {
+        let camera = std::mem::take(&mut self.scene.camera);
+        self.scene.camera = f(camera);
+    }
+
+    fn render(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+
+        let width: Option<u32> = line.parse("width")?;
+        let height: Option<u32> = line.parse("height")?;
+        let aspect: Option<f64> = line.aspect("aspect")?;
+        let samples: Option<u32> = line.parse("samples")?;
+        let depth: Option<u32> = line.parse("depth")?;
+        let seed: Option<u64> = line.parse("seed")?;
+        let threads: Option<usize> = line.parse("threads")?;
+
+        if width == Some(0) || height == Some(0) || samples == Some(0) {
+            return Err(line.error("'width', 'height' and 'samples' must be at least 1"));
+        }
+
+        let aspect: Option<f64> = match (width, height, aspect) {
+            (_, Some(_), Some(_)) => return Err(line.error("give either 'height' or 'aspect', not both")),
+            (Some(width), Some(height), None) => Some(width as f64 / height as f64),
+            (None, Some(_), None) => return Err(line.error("'height' requires 'width'")),
+            (_, None, aspect) => aspect,
+        };
+
+        self.update_camera(|mut camera| {
+            if let Some(width) = width {
+                camera = camera.image_width(width);
+            }
+            if let Some(aspect) = aspect {
+                camera = camera.aspect_ratio(aspect);
+            }
+            if let Some(samples) = samples {
+                camera = camera.samples_per_pixel(samples);
+            }
+            if let Some(depth) = depth {
+                camera = camera.max_depth(depth);
+            }
+            if let Some(seed) = seed {
+                camera = camera.seed(seed);
+            }
+            if let Some(threads) = threads {
+                camera = camera.threads(threads);
+            }
+            camera
+        });
+
+        Ok(())
+    }
+
+    fn output(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+
+        if let Some(exposure) = line.parse("exposure")? {
+            self.scene.output.exposure = exposure;
+        }
+        if let Some(tone_map) = line.take("tonemap") {
+            self.scene.output.tone_map = match tone_map {
+                "none" => ToneMap::None,
+                "reinhard" => ToneMap::Reinhard,
+                "aces" => ToneMap::AcesFilmic,
+                other => return Err(line.error(format!("unknown tone map '{}'", other))),
+            };
+        }
+        let gamma: Option<f64> = line.parse("gamma")?;
+        if gamma.is_some_and(|gamma| gamma <= 0_f64) {
+            return Err(line.error("'gamma' must be positive"));
+        }
+        if let Some(transfer) = line.take("transfer") {
+            self.scene.output.transfer = match transfer {
+                "linear" => TransferFunction::Linear,
+                "srgb" => TransferFunction::Srgb,
+                "gamma" => TransferFunction::Gamma(gamma.unwrap_or(2_f64)),
+                other => return Err(line.error(format!("unknown transfer function '{}'", other))),
+            };
+        } else if let Some(gamma) = gamma {
+            self.scene.output.transfer = TransferFunction::Gamma(gamma);
+        }
+
+        Ok(())
+    }
+
+    fn camera(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+
+        let look_from: Option<Vec3> = line.vec3("from")?;
+        let look_at: Option<Vec3> = line.vec3("at")?;
+        let vup: Option<Vec3> = line.vec3("up")?;
+        let vfov: Option<f64> = line.parse("vfov")?;
+        let defocus_angle: Option<f64> = line.parse("defocus")?;
+        let focus_dist: Option<f64> = line.parse("focus")?;
+        let shutter: Option<(f64, f64)> = match line.take("shutter") {
+            Some(value) => {
+                let parts: Vec<Option<f64>> = value.split(',').map(parse_number).collect();
+                match parts[..] {
+                    [Some(open), Some(close)] if open <= close => Some((open, close)),
+                    _ => return Err(line.error(format!("invalid shutter '{}', expected open,close", value))),
+                }
+            }
+            None => None,
+        };
+        if vfov.is_some_and(|vfov| !(vfov > 0_f64 && vfov < 180_f64)) {
+            return Err(line.error("'vfov' must be between 0 and 180 degrees"));
+        }
+
+        self.update_camera(|mut camera| {
+            if let Some(look_from) = look_from {
+                camera = camera.look_from(look_from);
+            }
+            if let Some(look_at) = look_at {
+                camera = camera.look_at(look_at);
+            }
+            if let Some(vup) = vup {
+                camera = camera.vup(vup);
+            }
+            if let Some(vfov) = vfov {
+                camera = camera.vfov(vfov);
+            }
+            if let Some(defocus_angle) = defocus_angle {
+                camera = camera.defocus_angle(defocus_angle);
+            }
+            if let Some(focus_dist) = focus_dist {
+                camera = camera.focus_dist(focus_dist);
+            }
+            if let Some((open, close)) = shutter {
+                camera = camera.shutter(open, close);
+            }
+            camera
+        });
+
+        match self.scene.camera.validate() {
+            Ok(()) => Ok(()),
+            Err(CameraError::NoViewDirection) => Err(line.error("'from' and 'at' must be different points")),
+            Err(CameraError::UpParallelToView) => Err(line.error("'up' must not be parallel to the view direction")),
+        }
+    }
+
+    fn background(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let color: Vec3 = line.require_vec3("color")?;
+
+        self.update_camera(|camera| camera.background(color));
+        Ok(())
+    }
+
+    fn environment(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(1)?;
+
+        let environment: Arc<dyn Environment> = match line.args[0] {
+            "constant" => Arc::new(ConstantEnvironment::new(line.require_vec3("color")?)),
+            "gradient" => Arc::new(GradientEnvironment::new(
+                line.require_vec3("bottom")?,
+                line.require_vec3("top")?,
+            )),
+            "sky" => {
+                let elevation: f64 = line.require("elevation")?;
+                let azimuth: f64 = line.parse("azimuth")?.unwrap_or(0_f64);
+                let turbidity: f64 = line.parse("turbidity")?.unwrap_or(3_f64);
+                let intensity: f64 = line.parse("intensity")?.unwrap_or(1_f64);
+                let sun_size: f64 = line.parse("sun_size")?.unwrap_or(0.5);
+                let sun_power: f64 = line.parse("sun_power")?.unwrap_or(5_f64);
+                Arc::new(
+                    SunSkyEnvironment::new(elevation, azimuth, turbidity)
+                        .sun(sun_size, sun_power)
+                        .intensity(intensity),
+                )
+            }
+            "hdri" => {
+                let Some(file) = line.take("file") else {
+                    return Err(line.error("missing property 'file'"));
+                };
+                let rotation: f64 = line.parse("rotation")?.unwrap_or(0_f64);
+                let intensity: f64 = line.parse("intensity")?.unwrap_or(1_f64);
+                let path: PathBuf = self.dir.join(file);
+                let hdri = HdriEnvironment::load(&path, rotation, intensity)
+                    .map_err(|err| line.error(format!("cannot load '{}': {}", path.display(), err)))?;
+                Arc::new(hdri)
+            }
+            other => return Err(line.error(format!("unknown environment type '{}'", other))),
+        };
+
+        self.update_camera(|camera| camera.environment(environment));
+        Ok(())
+    }
+
+    // Fog filling the whole scene; see `CameraBuilder::fog`.
+    fn fog(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let density: f64 = line.require("density")?;
+        let albedo: Color = line.vec3("albedo")?.unwrap_or(Color::new(1_f64, 1_f64, 1_f64));
+
+        if density < 0_f64 {
+            return Err(line.error("'density' must not be negative"));
+        }
+        self.update_camera(|camera| camera.fog(density, albedo));
+        Ok(())
+    }
+
+    fn texture(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(2)?;
+        let name: &str = line.args[0];
+        let kind: &str = line.args[1];
+        if self.textures.contains_key(name) {
+            return Err(line.error(format!("texture '{}' is already defined", name)));
+        }
+
+        let texture: Arc<dyn Texture> = match kind {
+            "solid" => Arc::new(SolidColor::new(line.require_vec3("color")?)),
+            "checker" => {
+                let scale: f64 = line.require("scale")?;
+                if !(scale > 0_f64 && scale.is_finite()) {
+                    return Err(line.error("'scale' must be positive"));
+                }
+                let even: Arc<dyn Texture> = self.texture_or_color(line, "even")?;
+                let odd: Arc<dyn Texture> = self.texture_or_color(line, "odd")?;
+                Arc::new(CheckerTexture::new(scale, even, odd))
+            }
+            "uvchecker" => {
+                let columns: f64 = line.require("columns")?;
+                let rows: f64 = line.parse("rows")?.unwrap_or(columns);
+                if !(columns > 0_f64 && rows > 0_f64) {
+                    return Err(line.error("'columns' and 'rows' must be positive"));
+                }
+                let even: Arc<dyn Texture> = self.texture_or_color(line, "even")?;
+                let odd: Arc<dyn Texture> = self.texture_or_color(line, "odd")?;
+                Arc::new(UvCheckerTexture::new(columns, rows, even, odd))
+            }
+            "image" => {
+                let Some(file) = line.take("file") else {
+                    return Err(line.error("missing property 'file'"));
+                };
+                let path: PathBuf = self.dir.join(file);
+                let image = ImageTexture::load(&path)
+                    .map_err(|err| line.error(format!("cannot load '{}': {}", path.display(), err)))?;
+                Arc::new(image)
+            }
+            "noise" => {
+                let scale: f64 = line.require("scale")?;
+                Arc::new(NoiseTexture::new(scale, &mut self.noise_sampler(line, name.as_bytes())?))
+            }
+            "marble" => {
+                let scale: f64 = line.require("scale")?;
+                let turbulence: f64 = line.parse("turbulence")?.unwrap_or(10_f64);
+                let color: Color = line.vec3("color")?.unwrap_or(Color::new(1.0, 1.0, 1.0));
+                Arc::new(MarbleTexture::new(scale, turbulence, color, &mut self.noise_sampler(line, name.as_bytes())?))
+            }
+            "wood" => {
+                let scale: f64 = line.require("scale")?;
+                let turbulence: f64 = line.parse("turbulence")?.unwrap_or(1_f64);
+                let light: Color = line.require_vec3("light")?;
+                let dark: Color = line.require_vec3("dark")?;
+                Arc::new(WoodTexture::new(scale, turbulence, light, dark, &mut self.noise_sampler(line, name.as_bytes())?))
+            }
+            other => return Err(line.error(format!("unknown texture type '{}'", other))),
+        };
+
+        self.textures.insert(name.to_string(), texture);
+        Ok(())
+    }
+
+    // The sampler generating a noise lattice. Without an explicit `seed`, it is seeded from
+    // the render seed and `key`, which identifies the texture or volume, so its noise does
+    // not change as other lines are added or removed.
+    fn noise_sampler(&self, line: &mut Line, key: &[u8]) -> Result<Sampler, SceneError> {
+        let seed: u64 = line.parse("seed")?.unwrap_or_else(|| self.seed ^ fnv1a(key));
+        Ok(Sampler::new(seed))
+    }
+
+    // A texture property, given either as the name of a texture or as a solid color.
+    fn texture_or_color(&self, line: &mut Line, key: &str) -> Result<Arc<dyn Texture>, SceneError> {
+        if let Some(texture) = line.props.get(key).and_then(|name| self.textures.get(*name)) {
+            line.take(key);
+            return Ok(Arc::clone(texture));
+        }
+        match line.vec3(key) {
+            Ok(Some(color)) => Ok(Arc::new(SolidColor::new(color))),
+            Ok(None) => Err(line.error(format!("missing property '{}'", key))),
+            Err(_) => Err(line.error(format!("'{}' is neither a texture nor a color", key))),
+        }
+    }
+
+    fn material(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(2)?;
+        let name: &str = line.args[0];
+        let kind: &str = line.args[1];
+        if self.materials.contains_key(name) {
+            return Err(line.error(format!("material '{}' is already defined", name)));
+        }
+
+        let material: Arc<dyn Material> = match kind {
+            "lambertian" => if line.props.contains_key("texture") {
+                Arc::new(Lambertian::from_texture(self.texture_or_color(line, "texture")?))
+            } else {
+                Arc::new(Lambertian::new(line.require_vec3("albedo")?))
+            },
+            "metal" => {
+                let albedo: Color = line.require_vec3("albedo")?;
+                let fuzz: f64 = line.parse("fuzz")?.unwrap_or(0_f64);
+                if !(0_f64..=1_f64).contains(&fuzz) {
+                    return Err(line.error("'fuzz' must be between 0 and 1"));
+                }
+                Arc::new(Metal::new(albedo, fuzz))
+            }
+            "dielectric" => {
+                let ior: f64 = line.require("ior")?;
+                if ior <= 0_f64 {
+                    return Err(line.error("'ior' must be positive"));
+                }
+                Arc::new(Dielectric::new(ior))
+            }
+            "light" => Arc::new(DiffuseLight::from_texture(self.texture_or_color(line, "emit")?)),
+            "isotropic" => Arc::new(Isotropic::from_texture(self.texture_or_color(line, "albedo")?)),
+            other => return Err(line.error(format!("unknown material type '{}'", other))),
+        };
+
+        if kind == "light" {
+            self.light_materials.push(Arc::clone(&material));
+        }
+        self.materials.insert(name.to_string(), material);
+        Ok(())
+    }
+
+    fn lookup_material(&self, line: &mut Line) -> Result<Arc<dyn Material>, SceneError> {
+        let Some(name) = line.take("material") else {
+            return Err(line.error("missing property 'material'"));
+        };
+        match self.materials.get(name) {
+            Some(material) => Ok(Arc::clone(material)),
+            None => Err(line.error(format!("undefined material '{}'", name))),
+        }
+    }
+
+    // Adds an object to the world, placed by the transform properties of its line. With a
+    // `density`, a solid object instead bounds a volume of that density, which scatters
+    // light with the object's material, normally an isotropic one. Objects that can be
+    // sampled are also added to the lights when their material is a light.
+    fn place(
+        &mut self,
+        line: &mut Line,
+        object: Arc<dyn Hittable>,
+        material: Arc<dyn Material>,
+        shape: Shape,
+    ) -> Result<(), SceneError> {
+        let transform: Option<Placing> = line.transform()?;
+        if shape != Shape::Solid && line.props.contains_key("density") {
+            return Err(line.error("'density' requires a closed shape, a sphere or a box"));
+        }
+        let density: Option<f64> = line.parse("density")?;
+
+        let placed: Arc<dyn Hittable> = placed(object, transform.as_ref());
+        match density {
+            Some(density) if !(density > 0_f64 && density.is_finite()) => {
+                return Err(line.error("'density' must be positive"));
+            }
+            Some(density) => {
+                self.scene.world.add(Arc::new(ConstantMedium::from_material(placed, density, material)));
+            }
+            None => {
+                if shape != Shape::Surface && self.light_materials.iter().any(|light| Arc::ptr_eq(light, &material)) {
+                    self.scene.lights.add(Arc::clone(&placed));
+                }
+                self.scene.world.add(placed);
+            }
+        }
+        Ok(())
+    }
+
+    fn sphere(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let center: Vec3 = line.require_vec3("center")?;
+        let center_end: Option<Vec3> = line.vec3("center_end")?;
+        let radius: f64 = line.require("radius")?;
+        let material: Arc<dyn Material> = self.lookup_material(line)?;
+
+        if radius <= 0_f64 {
+            return Err(line.error("'radius' must be positive"));
+        }
+        let sphere = Sphere::moving(center, center_end.unwrap_or(center), radius, Arc::clone(&material));
+        self.place(line, Arc::new(sphere), material, Shape::Solid)
+    }
+
+    fn triangle(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let a: Vec3 = line.require_vec3("a")?;
+        let b: Vec3 = line.require_vec3("b")?;
+        let c: Vec3 = line.require_vec3("c")?;
+        let material: Arc<dyn Material> = self.lookup_material(line)?;
+
+        self.place(line, Arc::new(Triangle::new(a, b, c, Arc::clone(&material))), material, Shape::Sampled)
+    }
+
+    fn quad(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let q: Vec3 = line.require_vec3("q")?;
+        let u: Vec3 = line.require_vec3("u")?;
+        let v: Vec3 = line.require_vec3("v")?;
+        let material: Arc<dyn Material> = self.lookup_material(line)?;
+
+        if Vec3::cross(&u, &v).near_zero() {
+            return Err(line.error("'u' and 'v' must span a nonzero area"));
+        }
+        self.place(line, Arc::new(Quad::new(q, u, v, Arc::clone(&material))), material, Shape::Sampled)
+    }
+
+    fn disk(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let center: Vec3 = line.require_vec3("center")?;
+        let normal: Vec3 = line.require_vec3("normal")?;
+        let radius: f64 = line.require("radius")?;
+        let material: Arc<dyn Material> = self.lookup_material(line)?;
+
+        if normal.near_zero() {
+            return Err(line.error("'normal' must not be zero"));
+        }
+        if radius <= 0_f64 {
+            return Err(line.error("'radius' must be positive"));
+        }
+        self.place(line, Arc::new(Disk::new(center, normal, radius, Arc::clone(&material))), material, Shape::Sampled)
+    }
+
+    fn plane(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let point: Vec3 = line.require_vec3("point")?;
+        let normal: Vec3 = line.require_vec3("normal")?;
+        let material: Arc<dyn Material> = self.lookup_material(line)?;
+
+        if normal.near_zero() {
+            return Err(line.error("'normal' must not be zero"));
+        }
+        self.place(line, Arc::new(Plane::new(point, normal, Arc::clone(&material))), material, Shape::Surface)
+    }
+
+    fn cuboid(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let min: Vec3 = line.require_vec3("min")?;
+        let max: Vec3 = line.require_vec3("max")?;
+        let material: Arc<dyn Material> = self.lookup_material(line)?;
+
+        if (0..3).any(|i| min[i] == max[i]) {
+            return Err(line.error("'min' and 'max' must differ along every axis"));
+        }
+        self.place(line, Arc::new(quad::make_box(min, max, Arc::clone(&material))), material, Shape::Solid)
+    }
+
+    // A heterogeneous medium filling the box from `min` to `max`, with its density given by
+    // a voxel grid read from a raw file, or by procedural noise. `density` scales the field
+    // into an extinction coefficient. With `emit`, the medium glows with that color, scaled
+    // by the density field or, for grids, by a second grid read from `emit_file`. Emitting
+    // media are black unless given an `albedo`, which must then be below one to let any
+    // light out.
+    fn volume(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(1)?;
+        let kind: &str = line.args[0];
+        let min: Vec3 = line.require_vec3("min")?;
+        let max: Vec3 = line.require_vec3("max")?;
+        let sigma: f64 = line.require("density")?;
+        let emit: Option<Color> = line.vec3("emit")?;
+        let albedo: Color = match line.vec3("albedo")? {
+            Some(albedo) if emit.is_some() && (0..3).all(|i| albedo[i] >= 1_f64) => {
+                return Err(line.error("'emit' requires an 'albedo' below one, or none at all"));
+            }
+            Some(albedo) => albedo,
+            None if emit.is_some() => Color::default(),
+            None => Color::new(1_f64, 1_f64, 1_f64),
+        };
+
+        if !(0..3).all(|axis| min[axis] < max[axis]) {
+            return Err(line.error("'min' must be below 'max' on every axis"));
+        }
+        if !(sigma >= 0_f64 && sigma.is_finite()) {
+            return Err(line.error("'density' must not be negative"));
+        }
+
+        let (field, emission): (Arc<dyn DensityField>, Option<Arc<dyn DensityField>>) = match kind {
+            "grid" => {
+                let resolution: Vec3 = line.require_vec3("resolution")?;
+                if !(0..3).all(|axis| resolution[axis] >= 1_f64 && resolution[axis].fract() == 0_f64) {
+                    return Err(line.error("'resolution' must be three positive integers"));
+                }
+                let resolution: [usize; 3] = [0, 1, 2].map(|axis| resolution[axis] as usize);
+                let format: RawFormat = match line.take("format").unwrap_or("f32") {
+                    "u8" => RawFormat::U8,
+                    "f32" => RawFormat::F32,
+                    other => return Err(line.error(format!("unknown voxel format '{}', expected u8 or f32", other))),
+                };
+
+                let mut load = |key: &str| -> Result<Option<Arc<dyn DensityField>>, SceneError> {
+                    let Some(file) = line.take(key) else {
+                        return Ok(None);
+                    };
+                    let path: PathBuf = self.dir.join(file);
+                    let grid = VoxelGrid::load_raw(&path, resolution, format)
+                        .map_err(|err| line.error(format!("cannot load '{}': {}", path.display(), err)))?;
+                    Ok(Some(Arc::new(grid)))
+                };
+                let Some(field) = load("file")? else {
+                    return Err(line.error("missing property 'file'"));
+                };
+                (field, load("emit_file")?)
+            }
+            "noise" => {
+                let scale: f64 = line.parse("scale")?.unwrap_or(4_f64);
+                if !(scale > 0_f64 && scale.is_finite()) {
+                    return Err(line.error("'scale' must be positive"));
+                }
+                // Volumes have no name, so they are told apart by where they are.
+                let key: Vec<u8> = [min, max].iter().flat_map(|c| c.e).flat_map(f64::to_le_bytes).collect();
+                let mut rng: Sampler = self.noise_sampler(line, &key)?;
+                (Arc::new(NoiseDensity::new(scale, &mut rng)), None)
+            }
+            other => return Err(line.error(format!("unknown volume type '{}'", other))),
+        };
+        let transform: Option<Placing> = line.transform()?;
+
+        let mut volume = HeterogeneousMedium::new(Arc::clone(&field), min, max, sigma, albedo);
+        match (emit, emission) {
+            (Some(color), emission) => volume = volume.with_emission(emission.unwrap_or(field), color),
+            (None, Some(_)) => return Err(line.error("'emit_file' requires 'emit'")),
+            (None, None) => {}
+        }
+        self.scene.world.add(placed(Arc::new(volume), transform.as_ref()));
+        Ok(())
+    }
+
+    // An OBJ model. `material` overrides the model's own materials, which otherwise fall
+    // back to a neutral gray; `group` restricts the model to one of its groups.
+    fn mesh(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(0)?;
+        let meshes: Vec<Mesh> = self.load_meshes(line)?;
+        let transform: Option<Placing> = line.transform()?;
+
+        for mesh in meshes {
+            self.scene.world.add(placed(Arc::new(mesh), transform.as_ref()));
+        }
+        Ok(())
+    }
+
+    // Declares an OBJ model, read with the same properties as `mesh`, that is not added to
+    // the world itself but placed by `instance` lines. The model is loaded once, and all of
+    // its instances share its triangles and BVH.
+    fn model(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(1)?;
+        let name: String = line.args[0].to_string();
+        if self.models.contains_key(&name) {
+            return Err(line.error(format!("model '{}' is already defined", name)));
+        }
+        let meshes: Vec<Arc<dyn Hittable>> = self
+            .load_meshes(line)?
+            .into_iter()
+            .map(|mesh| -> Arc<dyn Hittable> { Arc::new(mesh) })
+            .collect();
+
+        let model: Arc<dyn Hittable> = match meshes.len() {
+            1 => Arc::clone(&meshes[0]),
+            _ => Arc::new(BvhNode::from_objects(meshes)),
+        };
+        self.models.insert(name, model);
+        Ok(())
+    }
+
+    // A placement of a declared model, with an optional `material` replacing the model's own.
+    fn instance(&mut self, line: &mut Line) -> Result<(), SceneError> {
+        line.expect_args(1)?;
+        let name: &str = line.args[0];
+        let Some(model) = self.models.get(name).cloned() else {
+            return Err(line.error(format!("undefined model '{}'", name)));
+        };
+        let material: Option<Arc<dyn Material>> = if line.props.contains_key("material") {
+            Some(self.lookup_material(line)?)
+        } else {
+            None
+        };
+        let mut instance = match line.transform()? {
+            Some(Placing::Fixed(matrix)) => Instance::new(model, matrix),
+            Some(Placing::Moving(start, end)) => Instance::moving(model, start, end),
+            None => Instance::new(model, Mat4::IDENTITY),
+        };
+        if let Some(material) = material {
+            instance = instance.with_material(material);
+        }
+        self.scene.world.add(Arc::new(instance));
+        Ok(())
+    }
+
+    // Reads the OBJ file named by a `mesh` or `model` line, with one mesh per group.
+    fn load_meshes(&mut self, line: &mut Line) -> Result<Vec<Mesh>, SceneError> {
+        let Some(file) = line.take("file") else {
+            return Err(line.error("missing property 'file'"));
+        };
+        let material: Option<Arc<dyn Material>> = if line.props.contains_key("material") {
+            Some(self.lookup_material(line)?)
+        } else {
+            None
+        };
+        let group: Option<&str> = line.take("group");
+
+        let path: PathBuf = self.dir.join(file);
+        let model: ObjModel = obj::load_file(&path)
+            .map_err(|err| line.error(format!("cannot load '{}': {}", path.display(), err)))?;
+
+        let fallback: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+        let meshes: Vec<Mesh> = model
+            .groups
+            .iter()
+            .filter(|part| group.is_none_or(|name| part.name == name))
+            .map(|part| match &material {
+                Some(material) => Mesh::new(Arc::clone(&part.data), Arc::clone(material)),
+                None => part.to_mesh(&fallback),
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|err| line.error(format!("invalid mesh in '{}': {}", path.display(), err)))?;
+
+        match (meshes.is_empty(), group) {
+            (true, Some(name)) => Err(line.error(format!("'{}' has no group '{}'", path.display(), name))),
+            _ => Ok(meshes),
+        }
+    }
+}
+
+// 64-bit FNV-1a hash, which unlike the standard library's hashers is fixed.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x100_0000_01B3))
+}
+
+// Wraps an object in the transform a line gives it, if any.
+fn placed(object: Arc<dyn Hittable>, transform: Option<&Placing>) -> Arc<dyn Hittable> {
+    match transform {
+        Some(Placing::Fixed(matrix)) => Arc::new(Transform::new(object, *matrix)),
+        Some(Placing::Moving(start, end)) => Arc::new(MovingTransform::new(object, *start, *end)),
+        None => object,
+    }
+}
+
+// What `place` can do with an object besides adding it to the world.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    // A closed surface, which can bound a volume and be sampled as a light.
+    Solid,
+    // An open surface that can be sampled as a light.
+    Sampled,
+    // An open surface that can do neither, such as an infinite plane.
+    Surface,
+}
+
+// How a line places its object: by a fixed transform, or moving between two placements.
+enum Placing {
+    Fixed(Mat4),
+    Moving(Placement, Placement),
+}
+
+// A type a property can be parsed as.
+trait Value: FromStr {
+    fn is_valid(&self) -> bool {
+        true
+    }
+}
+
+// `f64::from_str` accepts "nan" and "inf", which no property can mean.
+impl Value for f64 {
+    fn is_valid(&self) -> bool {
+        self.is_finite()
+    }
+}
+
+impl Value for u32 {}
+impl Value for u64 {}
+impl Value for usize {}
+
+// Parses one number of a comma separated list, which must be finite like any `f64` property.
+fn parse_number(part: &str) -> Option<f64> {
+    part.trim().parse().ok().filter(|number: &f64| number.is_finite())
+}
+
+// A single tokenized directive. Properties are removed as they are read, so anything left
+// over when the directive is finished was not recognized.
+struct Line<'a> {
+    number: usize,
+    keyword: &'a str,
+    args: Vec<&'a str>,
+    props: BTreeMap<&'a str, &'a str>,
+}
+
+impl<'a> Line<'a> {
+    fn new(number: usize, text: &'a str) -> Result<Self, SceneError> {
+        let mut tokens = text.split_whitespace();
+        let keyword: &str = tokens.next().unwrap_or_default();
+
+        let mut args: Vec<&str> = Vec::new();
+        let mut props: BTreeMap<&str, &str> = BTreeMap::new();
+        for token in tokens {
+            match token.split_once('=') {
+                Some((key, value)) => {
+                    if key.is_empty() || value.is_empty() {
+                        return Err(SceneError::parse(number, format!("malformed property '{}'", token)));
+                    }
+                    if props.insert(key, value).is_some() {
+                        return Err(SceneError::parse(number, format!("duplicate property '{}'", key)));
+                    }
+                }
+                None if props.is_empty() => args.push(token),
+                None => {
+                    return Err(SceneError::parse(number, format!("unexpected argument '{}' after properties", token)));
+                }
+            }
+        }
+
+        Ok(Self { number, keyword, args, props })
+    }
+
+    fn error(&self, message: impl Into<String>) -> SceneError {
+        SceneError::parse(self.number, message)
+    }
+
+    fn expect_args(&self, count: usize) -> Result<(), SceneError> {
+        if self.args.len() != count {
+            return Err(self.error(format!(
+                "'{}' takes {} argument(s) before its properties, found {}",
+                self.keyword, count, self.args.len(),
+            )));
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), SceneError> {
+        match self.props.keys().next() {
+            Some(key) => Err(self.error(format!("unknown property '{}' for '{}'", key, self.keyword))),
+            None => Ok(()),
+        }
+    }
+
+    fn take(&mut self, key: &str) -> Option<&'a str> {
+        self.props.remove(key)
+    }
+
+    fn parse<T: Value>(&mut self, key: &str) -> Result<Option<T>, SceneError> {
+        match self.take(key) {
+            Some(value) => match value.parse::<T>() {
+                Ok(parsed) if parsed.is_valid() => Ok(Some(parsed)),
+                _ => Err(self.error(format!("invalid value '{}' for '{}'", value, key))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn require<T: Value>(&mut self, key: &str) -> Result<T, SceneError> {
+        match self.parse(key)? {
+            Some(value) => Ok(value),
+            None => Err(self.error(format!("missing property '{}'", key))),
+        }
+    }
+
+    fn vec3(&mut self, key: &str) -> Result<Option<Vec3>, SceneError> {
+        let Some(value) = self.take(key) else {
+            return Ok(None);
+        };
+
+        let parts: Vec<Option<f64>> = value.split(',').map(parse_number).collect();
+        match parts[..] {
+            [Some(x), Some(y), Some(z)] => Ok(Some(Vec3::new(x, y, z))),
+            _ => Err(self.error(format!("invalid vector '{}' for '{}', expected x,y,z", value, key))),
+        }
+    }
+
+    fn require_vec3(&mut self, key: &str) -> Result<Vec3, SceneError> {
+        match self.vec3(key)? {
+            Some(value) => Ok(value),
+            None => Err(self.error(format!("missing property '{}'", key))),
+        }
+    }
+
+    // The placement of an object, given either by `matrix`, an affine matrix written as 12
+    // or 16 comma separated values in row-major order, or by any of `scale` (a factor or
+    // x,y,z factors), `rotate` (x,y,z angles in degrees, applied about x, then y, then z)
+    // and `translate`, applied in that order. Any of `scale_end`, `rotate_end` and
+    // `translate_end` make the object move from the first placement at time 0 to the second
+    // at time 1, with the parts not given staying the same.
+    fn transform(&mut self) -> Result<Option<Placing>, SceneError> {
+        let matrix: Option<Mat4> = match self.take("matrix") {
+            Some(value) => {
+                let values: Vec<Option<f64>> = value.split(',').map(parse_number).collect();
+                let values: Option<Vec<f64>> = values.into_iter().collect();
+                let mut m: [[f64; 4]; 4] = Mat4::IDENTITY.m;
+                match values.as_deref() {
+                    Some(values) if values.len() == 12 || values.len() == 16 => {
+                        for (i, value) in values.iter().enumerate() {
+                            m[i / 4][i % 4] = *value;
+                        }
+                    }
+                    _ => return Err(self.error(format!("invalid matrix '{}', expected 12 or 16 values", value))),
+                }
+                Some(Mat4::new(m))
+            }
+            None => None,
+        };
+
+        let start: Option<Placement> = self.placement(["scale", "rotate", "translate"], &Placement::default())?;
+        let end: Option<Placement> =
+            self.placement(["scale_end", "rotate_end", "translate_end"], &start.unwrap_or_default())?;
+
+        let transform: Option<Placing> = match (matrix, start, end) {
+            (Some(_), None, None) => matrix.map(Placing::Fixed),
+            (Some(_), _, _) => {
+                return Err(self.error("'matrix' cannot be combined with 'scale', 'rotate' or 'translate'"));
+            }
+            (None, start, None) => start.map(|start| Placing::Fixed(start.matrix())),
+            (None, start, Some(end)) => {
+                let start: Placement = start.unwrap_or_default();
+                Some(Placing::Moving(start, end))
+            }
+        };
+        match &transform {
+            Some(Placing::Fixed(m)) => {
+                if m.m[3] != [0_f64, 0_f64, 0_f64, 1_f64] {
+                    return Err(self.error("the transform is not affine"));
+                }
+                if m.inverse().is_none() {
+                    return Err(self.error("the transform is singular"));
+                }
+            }
+            Some(Placing::Moving(start, end)) => {
+                if (0..3).any(|i| start.scale[i] == 0_f64 || end.scale[i] == 0_f64) {
+                    return Err(self.error("the transform is singular"));
+                }
+                if (0..3).any(|i| start.scale[i] * end.scale[i] < 0_f64) {
+                    return Err(self.error("a moving scale cannot change sign"));
+                }
+            }
+            None => {}
+        }
+        Ok(transform)
+    }
+
+    // A placement read from the given scale, rotate and translate properties, if any are set,
+    // taking the parts that are not set from `base`.
+    fn placement(&mut self, keys: [&str; 3], base: &Placement) -> Result<Option<Placement>, SceneError> {
+        let [scale, rotate, translate] = keys;
+        let scale_factors: Option<Vec3> = match self.props.get(scale).and_then(|value| parse_number(value)) {
+            Some(factor) => {
+                self.take(scale);
+                Some(Vec3::new(factor, factor, factor))
+            }
+            _ => self.vec3(scale)?,
+        };
+        let rotate: Option<Vec3> = self.vec3(rotate)?;
+        let translate: Option<Vec3> = self.vec3(translate)?;
+
+        Ok(match (scale_factors, rotate, translate) {
+            (None, None, None) => None,
+            _ => Some(Placement {
+                scale: scale_factors.unwrap_or(base.scale),
+                rotate: rotate.unwrap_or(base.rotate),
+                translate: translate.unwrap_or(base.translate),
+            }),
+        })
+    }
+
+    // An aspect ratio, written either as a number or as `width:height`.
+    fn aspect(&mut self, key: &str) -> Result<Option<f64>, SceneError> {
+        let Some(value) = self.take(key) else {
+            return Ok(None);
+        };
+
+        match parse_aspect_ratio(value) {
+            Some(aspect) => Ok(Some(aspect)),
+            None => Err(self.error(format!("invalid aspect ratio '{}'", value))),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod error;
+pub mod loader;
+pub mod mtl;
+pub mod obj;
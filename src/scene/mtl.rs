@@ -0,0 +1,151 @@
+// Loader for Wavefront MTL material libraries.
+//
+// Each `newmtl` entry is mapped onto the closest of the renderer's materials:
+//
+//   - transparent entries (`d` < 1, `Tr` > 0, or `illum` 4, 6 or 7) become dielectrics
+//     with the `Ni` index of refraction,
+//   - reflective entries (`illum` 3) become metals tinted by `Ks`, with a fuzz derived
+//     from the `Ns` specular exponent,
+//   - everything else becomes a lambertian with the `Kd` albedo, or the `map_Kd` image
+//     when there is one.
+//
+// Statements the renderer has no use for, such as other texture maps, are ignored.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::geometry::vec3::Color;
+
+use crate::materials::dielectric::Dielectric;
+use crate::materials::lambertian::Lambertian;
+use crate::materials::material::Material;
+use crate::materials::metal::Metal;
+
+use crate::textures::image_texture::ImageTexture;
+use crate::textures::texture::Texture;
+
+use super::error::SceneError;
+
+pub type MaterialLibrary = HashMap<String, Arc<dyn Material>>;
+
+// Texture maps are read relative to `dir`.
+pub fn parse(source: &str, dir: &Path) -> Result<MaterialLibrary, SceneError> {
+    let mut library: MaterialLibrary = HashMap::new();
+    let mut current: Option<(String, Entry)> = None;
+
+    for (index, raw) in source.lines().enumerate() {
+        let number: usize = index + 1;
+        let text: &str = match raw.find('#') {
+            Some(comment) => &raw[..comment],
+            None => raw,
+        };
+        let mut tokens = text.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let args: Vec<&str> = tokens.collect();
+
+        if keyword == "newmtl" {
+            if let Some((name, entry)) = current.take() {
+                library.insert(name, entry.material());
+            }
+            if args.is_empty() {
+                return Err(SceneError::parse(number, "'newmtl' requires a name"));
+            }
+            current = Some((args.join(" "), Entry::default()));
+            continue;
+        }
+
+        let Some((_, entry)) = current.as_mut() else {
+            // Statements before the first `newmtl` have nothing to apply to.
+            continue;
+        };
+        match keyword {
+            "Kd" => entry.diffuse = color(number, keyword, &args)?,
+            "Ks" => entry.specular = color(number, keyword, &args)?,
+            "Ns" => entry.exponent = scalar(number, keyword, &args)?,
+            "Ni" => entry.ior = scalar(number, keyword, &args)?,
+            "d" => entry.dissolve = scalar(number, keyword, &args)?,
+            "Tr" => entry.dissolve = 1_f64 - scalar::<f64>(number, keyword, &args)?,
+            "illum" => entry.illum = scalar::<u32>(number, keyword, &args)?,
+            "map_Kd" => {
+                // Map options come before the file name; none of them are supported.
+                let Some(file) = args.last() else {
+                    return Err(SceneError::parse(number, "'map_Kd' requires a file name"));
+                };
+                let path = dir.join(file);
+                let image = ImageTexture::load(&path).map_err(|err| {
+                    SceneError::parse(number, format!("cannot load '{}': {}", path.display(), err))
+                })?;
+                entry.diffuse_map = Some(Arc::new(image));
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((name, entry)) = current {
+        library.insert(name, entry.material());
+    }
+    Ok(library)
+}
+
+// The statements of one `newmtl` entry, with the defaults of the MTL format.
+struct Entry {
+    diffuse: Color,
+    diffuse_map: Option<Arc<dyn Texture>>,
+    specular: Color,
+    exponent: f64,
+    ior: f64,
+    dissolve: f64,
+    illum: u32,
+}
+
+impl Default for Entry {
+    fn default() -> Self {
+        Self {
+            diffuse: Color::new(0.8, 0.8, 0.8),
+            diffuse_map: None,
+            specular: Color::new(0.0, 0.0, 0.0),
+            exponent: 0_f64,
+            ior: 1.5,
+            dissolve: 1_f64,
+            illum: 2,
+        }
+    }
+}
+
+impl Entry {
+    fn material(&self) -> Arc<dyn Material> {
+        if self.dissolve < 1_f64 || matches!(self.illum, 4 | 6 | 7) {
+            Arc::new(Dielectric::new(self.ior))
+        } else if self.illum == 3 {
+            // A Phong exponent of 0 is a fully rough lobe; sharper lobes shrink the fuzz.
+            let fuzz: f64 = (2_f64 / (self.exponent.max(0_f64) + 2_f64)).sqrt();
+            Arc::new(Metal::new(self.specular, fuzz))
+        } else if let Some(map) = &self.diffuse_map {
+            Arc::new(Lambertian::from_texture(Arc::clone(map)))
+        } else {
+            Arc::new(Lambertian::new(self.diffuse))
+        }
+    }
+}
+
+fn scalar<T: std::str::FromStr>(number: usize, keyword: &str, args: &[&str]) -> Result<T, SceneError> {
+    match args {
+        [value] => value
+            .parse()
+            .map_err(|_| SceneError::parse(number, format!("invalid value '{}' for '{}'", value, keyword))),
+        _ => Err(SceneError::parse(number, format!("'{}' takes a single value", keyword))),
+    }
+}
+
+// A color written as `r g b`, or as a single value for a gray.
+fn color(number: usize, keyword: &str, args: &[&str]) -> Result<Color, SceneError> {
+    let values: Vec<Option<f64>> = args.iter().map(|arg| arg.parse().ok()).collect();
+    match values[..] {
+        [Some(r)] => Ok(Color::new(r, r, r)),
+        [Some(r), Some(g), Some(b)] => Ok(Color::new(r, g, b)),
+        _ => Err(SceneError::parse(number, format!("invalid color '{}' for '{}'", args.join(" "), keyword))),
+    }
+}
@@ -0,0 +1,247 @@
+// Loader for Wavefront OBJ models.
+//
+// Supported statements are `v`, `vt`, `vn`, `f`, `g`, `o`, `usemtl` and `mtllib`; anything
+// else (smoothing groups, lines, free-form geometry) is ignored. Faces with more than three
+// vertices are fan triangulated, so polygons are expected to be convex. Vertex references
+// may be negative, counting back from the most recent vertex.
+//
+// Every group is split further by material, and each part becomes one `MeshData`. Material
+// libraries named by `mtllib` are read relative to the model's directory.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::hittables::mesh::{Mesh, MeshData, MeshError};
+
+use crate::materials::material::Material;
+
+use super::error::SceneError;
+use super::mtl::{self, MaterialLibrary};
+
+// A part of a model sharing one group and one material.
+pub struct ObjGroup {
+    pub name: String,
+    // The material named by `usemtl`, when it was found in a loaded library.
+    pub material: Option<Arc<dyn Material>>,
+    pub data: Arc<MeshData>,
+}
+
+impl ObjGroup {
+    pub fn to_mesh(&self, fallback: &Arc<dyn Material>) -> Result<Mesh, MeshError> {
+        let material: &Arc<dyn Material> = self.material.as_ref().unwrap_or(fallback);
+        Mesh::new(Arc::clone(&self.data), Arc::clone(material))
+    }
+}
+
+pub struct ObjModel {
+    pub groups: Vec<ObjGroup>,
+}
+
+pub fn load_file(path: &Path) -> Result<ObjModel, SceneError> {
+    let source: String = fs::read_to_string(path)?;
+    parse(&source, path.parent().unwrap_or(Path::new("")))
+}
+
+pub fn parse(source: &str, dir: &Path) -> Result<ObjModel, SceneError> {
+    let mut parser = Parser::new(dir);
+    for (index, raw) in source.lines().enumerate() {
+        let text: &str = match raw.find('#') {
+            Some(comment) => &raw[..comment],
+            None => raw,
+        };
+        let mut tokens = text.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let args: Vec<&str> = tokens.collect();
+        parser.statement(index + 1, keyword, &args)?;
+    }
+
+    parser.flush();
+    Ok(ObjModel { groups: parser.groups })
+}
+
+// A face corner: indices of its position, texture coordinate and normal.
+type Corner = (usize, Option<usize>, Option<usize>);
+
+struct Parser<'a> {
+    dir: &'a Path,
+    positions: Vec<Point3>,
+    uvs: Vec<[f64; 2]>,
+    normals: Vec<Vec3>,
+    library: MaterialLibrary,
+    groups: Vec<ObjGroup>,
+    // The group being built, and its triangles so far.
+    name: String,
+    material: Option<Arc<dyn Material>>,
+    triangles: Vec<[Corner; 3]>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(dir: &'a Path) -> Self {
+        Self {
+            dir,
+            positions: Vec::new(),
+            uvs: Vec::new(),
+            normals: Vec::new(),
+            library: MaterialLibrary::new(),
+            groups: Vec::new(),
+            name: String::from("default"),
+            material: None,
+            triangles: Vec::new(),
+        }
+    }
+
+    fn statement(&mut self, number: usize, keyword: &str, args: &[&str]) -> Result<(), SceneError> {
+        match keyword {
+            "v" => {
+                // An optional fourth weight is ignored.
+                let v: Vec<f64> = floats(number, keyword, args, 3, 4)?;
+                self.positions.push(Point3::new(v[0], v[1], v[2]));
+            }
+            "vt" => {
+                let uv: Vec<f64> = floats(number, keyword, args, 1, 3)?;
+                self.uvs.push([uv[0], uv.get(1).copied().unwrap_or(0_f64)]);
+            }
+            "vn" => {
+                let n: Vec<f64> = floats(number, keyword, args, 3, 3)?;
+                self.normals.push(Vec3::new(n[0], n[1], n[2]));
+            }
+            "f" => self.face(number, args)?,
+            "g" | "o" => {
+                self.flush();
+                self.name = if args.is_empty() { String::from("default") } else { args.join(" ") };
+            }
+            "usemtl" => {
+                self.flush();
+                // Unknown names are left to the caller's fallback material.
+                self.material = self.library.get(&args.join(" ")).cloned();
+            }
+            "mtllib" => {
+                for file in args {
+                    let path = self.dir.join(file);
+                    let source: String = fs::read_to_string(&path).map_err(|err| {
+                        SceneError::parse(number, format!("cannot read '{}': {}", path.display(), err))
+                    })?;
+                    let dir: &Path = path.parent().unwrap_or(self.dir);
+                    let library: MaterialLibrary = mtl::parse(&source, dir).map_err(|err| {
+                        SceneError::parse(number, format!("in '{}': {}", path.display(), err))
+                    })?;
+                    self.library.extend(library);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn face(&mut self, number: usize, args: &[&str]) -> Result<(), SceneError> {
+        if args.len() < 3 {
+            return Err(SceneError::parse(number, "a face needs at least three vertices"));
+        }
+
+        let corners: Vec<Corner> = args
+            .iter()
+            .map(|arg| self.corner(number, arg))
+            .collect::<Result<_, _>>()?;
+        for i in 1..corners.len() - 1 {
+            self.triangles.push([corners[0], corners[i], corners[i + 1]]);
+        }
+        Ok(())
+    }
+
+    // Resolves a `v`, `v/vt`, `v//vn` or `v/vt/vn` reference.
+    fn corner(&self, number: usize, text: &str) -> Result<Corner, SceneError> {
+        let mut parts = text.split('/');
+        let position: usize = resolve(number, text, parts.next(), self.positions.len())?
+            .ok_or_else(|| SceneError::parse(number, format!("face vertex '{}' has no position", text)))?;
+        let uv: Option<usize> = resolve(number, text, parts.next(), self.uvs.len())?;
+        let normal: Option<usize> = resolve(number, text, parts.next(), self.normals.len())?;
+        if parts.next().is_some() {
+            return Err(SceneError::parse(number, format!("invalid face vertex '{}'", text)));
+        }
+        Ok((position, uv, normal))
+    }
+
+    // Turns the triangles collected so far into a group. Texture coordinates and normals
+    // are kept only when every corner has them, and corners sharing all their indices
+    // share a vertex.
+    fn flush(&mut self) {
+        if self.triangles.is_empty() {
+            return;
+        }
+        let corners = self.triangles.iter().flatten();
+        let has_uvs: bool = corners.clone().all(|corner| corner.1.is_some());
+        let has_normals: bool = corners.clone().all(|corner| corner.2.is_some());
+
+        let mut vertices: HashMap<Corner, usize> = HashMap::new();
+        let mut positions: Vec<Point3> = Vec::new();
+        let mut uvs: Vec<[f64; 2]> = Vec::new();
+        let mut normals: Vec<Vec3> = Vec::new();
+        let mut indices: Vec<[usize; 3]> = Vec::with_capacity(self.triangles.len());
+
+        for triangle in self.triangles.drain(..) {
+            indices.push(triangle.map(|(position, uv, normal)| {
+                let key: Corner = (position, uv.filter(|_| has_uvs), normal.filter(|_| has_normals));
+                *vertices.entry(key).or_insert_with(|| {
+                    positions.push(self.positions[position]);
+                    if let Some(uv) = key.1 {
+                        uvs.push(self.uvs[uv]);
+                    }
+                    if let Some(normal) = key.2 {
+                        normals.push(self.normals[normal]);
+                    }
+                    positions.len() - 1
+                })
+            }));
+        }
+
+        let mut data = MeshData::new(positions, indices);
+        if has_uvs {
+            data = data.with_uvs(uvs);
+        }
+        if has_normals {
+            data = data.with_normals(normals);
+        }
+        self.groups.push(ObjGroup {
+            name: self.name.clone(),
+            material: self.material.clone(),
+            data: Arc::new(data),
+        });
+    }
+}
+
+// Parses between `min` and `max` numbers.
+fn floats(number: usize, keyword: &str, args: &[&str], min: usize, max: usize) -> Result<Vec<f64>, SceneError> {
+    if args.len() < min || args.len() > max {
+        return Err(SceneError::parse(number, format!("'{}' takes {} to {} values", keyword, min, max)));
+    }
+    args.iter()
+        .map(|arg| arg.parse().map_err(|_| SceneError::parse(number, format!("invalid number '{}'", arg))))
+        .collect()
+}
+
+// Converts a 1-based, possibly negative, OBJ index into a 0-based one. Empty parts of a
+// face vertex (as in `v//vn`) resolve to `None`.
+fn resolve(number: usize, text: &str, part: Option<&str>, count: usize) -> Result<Option<usize>, SceneError> {
+    let Some(part) = part.filter(|part| !part.is_empty()) else {
+        return Ok(None);
+    };
+    let index: i64 = part
+        .parse()
+        .map_err(|_| SceneError::parse(number, format!("invalid face vertex '{}'", text)))?;
+
+    let resolved: Option<usize> = match index {
+        i if i > 0 => Some(i as usize - 1),
+        i if i < 0 => count.checked_sub(i.unsigned_abs() as usize),
+        _ => None,
+    };
+    match resolved.filter(|&i| i < count) {
+        Some(i) => Ok(Some(i)),
+        None => Err(SceneError::parse(number, format!("face vertex '{}' is out of range", text))),
+    }
+}
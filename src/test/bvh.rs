@@ -0,0 +1,113 @@
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::aabb::Aabb;
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::bvh::BvhNode;
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::hittable_list::HittableList;
+#[allow(unused_imports)]
+use crate::hittables::sphere::Sphere;
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+
+#[allow(dead_code)]
+fn random_spheres(count: usize, rng: &mut Sampler) -> HittableList {
+    let material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    let mut list = HittableList::new();
+    for _ in 0..count {
+        list.add(Arc::new(Sphere::new(
+            Vec3::random_range(rng, -10.0, 10.0),
+            rng.random_double_range(0.05, 1.0),
+            material.clone(),
+        )));
+    }
+    list
+}
+
+#[test]
+fn test_aabb_hit() {
+    let bbox = Aabb::from_points(Point3::new(1.0, 1.0, 1.0), Point3::new(-1.0, -1.0, -1.0));
+    let t = Interval::new(0.0, f64::INFINITY);
+
+    assert!(bbox.hit(&Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0)), t));
+    assert!(!bbox.hit(&Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, 1.0)), t), "Box behind the ray was hit");
+    assert!(!bbox.hit(&Ray::new(Point3::new(2.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0)), t), "Ray beside the box hit it");
+    assert!(!bbox.hit(&Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0)), Interval::new(0.0, 3.0)), "Box beyond ray_t was hit");
+}
+
+#[test]
+fn test_aabb_enclosing_and_axes() {
+    let a = Aabb::from_points(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+    let b = Aabb::from_points(Point3::new(2.0, -1.0, 0.0), Point3::new(3.0, 0.0, 1.0));
+    let both = Aabb::enclosing(&a, &b);
+
+    assert_eq!(both.x, Interval::new(0.0, 3.0));
+    assert_eq!(both.y, Interval::new(-1.0, 1.0));
+    assert_eq!(both.longest_axis(), 0);
+    assert_eq!(both.centroid().e, [1.5, 0.0, 0.5]);
+    assert_eq!(a.surface_area(), 6.0);
+    assert_eq!(Aabb::enclosing(&Aabb::EMPTY, &a), a);
+}
+
+#[test]
+fn test_aabb_pads_flat_boxes() {
+    let flat = Aabb::from_points(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0));
+    assert!(flat.z.size() > 0.0);
+    assert!(flat.hit(&Ray::new(Point3::new(0.5, 0.5, 1.0), Vec3::new(0.0, 0.0, -1.0)), Interval::new(0.0, 2.0)));
+}
+
+#[test]
+fn test_bvh_matches_linear_list() {
+    let mut rng = Sampler::new(11);
+    let list = random_spheres(500, &mut rng);
+    let bvh = BvhNode::new(random_spheres(500, &mut Sampler::new(11)));
+    assert_eq!(bvh.bounding_box(), list.bounding_box());
+
+    let t = Interval::new(0.001, f64::INFINITY);
+    let mut hits = 0;
+    for _ in 0..2000 {
+        let r = Ray::new(Vec3::random_range(&mut rng, -15.0, 15.0), Vec3::random_unit_vector(&mut rng));
+        let mut list_rec = HitRecord::default();
+        let mut bvh_rec = HitRecord::default();
+
+        let list_hit = list.hit(&r, &t, &mut list_rec, &mut Sampler::new(0));
+        assert_eq!(list_hit, bvh.hit(&r, &t, &mut bvh_rec, &mut Sampler::new(0)));
+        if list_hit {
+            hits += 1;
+            assert_eq!(list_rec.t, bvh_rec.t);
+            assert_eq!(list_rec.p, bvh_rec.p);
+        }
+    }
+    assert!(hits > 100, "Too few rays hit the test scene to be meaningful");
+}
+
+#[test]
+fn test_bvh_small_and_degenerate() {
+    let material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    let r = Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+    let t = Interval::new(0.001, f64::INFINITY);
+    let mut rec = HitRecord::default();
+
+    assert!(!BvhNode::new(HittableList::new()).hit(&r, &t, &mut rec, &mut Sampler::new(0)));
+
+    // Coincident spheres cannot be separated by the heuristic.
+    let mut list = HittableList::new();
+    for radius in [0.5, 1.0, 0.25, 0.75] {
+        list.add(Arc::new(Sphere::new(Point3::new(0.0, 0.0, 0.0), radius, material.clone())));
+    }
+    let bvh = BvhNode::new(list);
+    assert!(bvh.hit(&r, &t, &mut rec, &mut Sampler::new(0)));
+    assert!((rec.t - 4.0).abs() < 1e-12);
+}
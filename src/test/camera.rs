@@ -0,0 +1,292 @@
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::camera::camera::Camera;
+#[allow(unused_imports)]
+use crate::camera::error::CameraError;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::hittable::Hittable;
+#[allow(unused_imports)]
+use crate::hittables::hittable_list::HittableList;
+#[allow(unused_imports)]
+use crate::hittables::quad::Quad;
+#[allow(unused_imports)]
+use crate::hittables::sphere::Sphere;
+#[allow(unused_imports)]
+use crate::materials::dielectric::Dielectric;
+#[allow(unused_imports)]
+use crate::materials::diffuse_light::DiffuseLight;
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+
+#[allow(dead_code)]
+fn test_world() -> HittableList {
+    let mut world = HittableList::new();
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, -100.5, -1.0),
+        100.0,
+        Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.0))),
+    )));
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 0.0, -1.0),
+        0.5,
+        Arc::new(Dielectric::new(1.5)),
+    )));
+    world
+}
+
+#[test]
+fn test_render_is_deterministic_across_thread_counts() {
+    let world = test_world();
+    let render = |threads: usize| {
+        Camera::builder()
+            .image_width(70)
+            .aspect_ratio(1.4)
+            .samples_per_pixel(4)
+            .threads(threads)
+            .seed(7)
+            .build()
+            .unwrap()
+            .render(&world)
+    };
+
+    let single = render(1);
+    let multi = render(4);
+    assert_eq!((single.width(), single.height()), (70, 50));
+    assert!(single == multi, "Renders with the same seed differ between thread counts");
+}
+
+#[test]
+fn test_render_depends_on_seed() {
+    let world = test_world();
+    let render = |seed: u64| {
+        Camera::builder()
+            .image_width(16)
+            .samples_per_pixel(2)
+            .threads(2)
+            .seed(seed)
+            .build()
+            .unwrap()
+            .render(&world)
+    };
+
+    assert!(render(1) != render(2), "Renders with different seeds are identical");
+}
+
+#[test]
+fn test_background_color() {
+    let framebuffer = Camera::builder()
+        .image_width(8)
+        .samples_per_pixel(2)
+        .background(Color::new(0.25, 0.5, 1.0))
+        .build()
+        .unwrap()
+        .render(&HittableList::new());
+
+    assert_eq!(framebuffer.get(3, 5).e, [0.25, 0.5, 1.0]);
+}
+
+#[test]
+fn test_emission() {
+    // Inside a glowing sphere in the dark, every camera ray sees exactly the emitted light.
+    let mut world = HittableList::new();
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 0.0, 0.0),
+        10.0,
+        Arc::new(DiffuseLight::new(Color::new(4.0, 2.0, 1.0))),
+    )));
+
+    let framebuffer = Camera::builder()
+        .image_width(8)
+        .samples_per_pixel(3)
+        .background(Color::new(0.0, 0.0, 0.0))
+        .build()
+        .unwrap()
+        .render(&world);
+
+    for (i, j) in [(0, 0), (4, 4), (7, 2)] {
+        let pixel: Color = framebuffer.get(i, j);
+        assert!((0..3).all(|c| (pixel[c] - [4.0, 2.0, 1.0][c]).abs() < 1e-12), "{:?}", pixel.e);
+    }
+}
+
+#[test]
+fn test_motion_blur() {
+    // A small light sweeps across the view. With the shutter open over its whole path, the
+    // center pixel sees it for only a fraction of the time.
+    let mut world = HittableList::new();
+    world.add(Arc::new(Sphere::moving(
+        Point3::new(-1.0, 0.0, -1.0),
+        Point3::new(1.0, 0.0, -1.0),
+        0.2,
+        Arc::new(DiffuseLight::new(Color::new(1.0, 1.0, 1.0))),
+    )));
+
+    let render = |open: f64, close: f64| {
+        Camera::builder()
+            .image_width(9)
+            .samples_per_pixel(64)
+            .background(Color::new(0.0, 0.0, 0.0))
+            .shutter(open, close)
+            .seed(3)
+            .build()
+            .unwrap()
+            .render(&world)
+    };
+
+    assert_eq!(render(0.0, 0.0).get(4, 4).e, [0.0, 0.0, 0.0]);
+    assert_eq!(render(0.5, 0.5).get(4, 4).e, [1.0, 1.0, 1.0]);
+
+    let blurred: f64 = render(0.0, 1.0).get(4, 4).x();
+    assert!(blurred > 0.05 && blurred < 0.5, "{}", blurred);
+}
+
+#[test]
+fn test_fog() {
+    // Inside a glowing sphere of radius 10, fog that absorbs everything it scatters lets
+    // through exp(-density * 10) of the light.
+    let mut world = HittableList::new();
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 0.0, 0.0),
+        10.0,
+        Arc::new(DiffuseLight::new(Color::new(1.0, 1.0, 1.0))),
+    )));
+
+    let render = |albedo: f64| {
+        Camera::builder()
+            .image_width(4)
+            .samples_per_pixel(500)
+            .max_depth(50)
+            .background(Color::new(0.0, 0.0, 0.0))
+            .fog(0.1, Color::new(albedo, albedo, albedo))
+            .seed(1)
+            .build()
+            .unwrap()
+            .render(&world)
+    };
+
+    let absorbing: f64 = render(0.0).get(2, 2).x();
+    assert!((absorbing - (-1.0_f64).exp()).abs() < 0.05, "{}", absorbing);
+
+    // Fog that scatters everything only redirects the light, which all comes from the sphere.
+    let scattering: f64 = render(1.0).get(2, 2).x();
+    assert!((scattering - 1.0).abs() < 1e-9, "{}", scattering);
+}
+
+#[test]
+fn test_light_sampling() {
+    // A small sphere light above a white floor. Straight below it, the floor reflects
+    // albedo * emit * r^2 / d^2 = 0.5 * 400 * 0.1^2 / 2^2 = 0.5, and with the light sampled
+    // directly every sample finds it.
+    let light: Arc<dyn Hittable> = Arc::new(Sphere::new(
+        Point3::new(0.0, 2.0, 0.0),
+        0.1,
+        Arc::new(DiffuseLight::new(Color::new(400.0, 400.0, 400.0))),
+    ));
+    let mut world = HittableList::new();
+    world.add(Arc::new(Quad::new(
+        Point3::new(-50.0, 0.0, -50.0),
+        Vec3::new(100.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 100.0),
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    )));
+    world.add(Arc::clone(&light));
+    let mut lights = HittableList::new();
+    lights.add(light);
+
+    let framebuffer = Camera::builder()
+        .image_width(5)
+        .samples_per_pixel(16)
+        .vfov(1.0)
+        .look_from(Point3::new(0.0, 1.0, 0.0))
+        .look_at(Point3::new(0.0, 0.0, 0.0))
+        .vup(Vec3::new(0.0, 0.0, -1.0))
+        .background(Color::new(0.0, 0.0, 0.0))
+        .lights(lights)
+        .seed(5)
+        .build()
+        .unwrap()
+        .render(&world);
+
+    for (i, j) in [(0, 0), (2, 2), (4, 1)] {
+        let pixel: f64 = framebuffer.get(i, j).x();
+        assert!((pixel - 0.5).abs() < 0.01, "{}", pixel);
+    }
+}
+
+#[test]
+fn test_environment_sampling() {
+    // A lambertian sphere under a uniform environment reflects albedo times its radiance,
+    // found both by sampling the environment and by following the bounces.
+    let mut world = HittableList::new();
+    world.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 0.0, -3.0),
+        1.0,
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    )));
+
+    let framebuffer = Camera::builder()
+        .image_width(5)
+        .samples_per_pixel(256)
+        .vfov(10.0)
+        .background(Color::new(1.0, 1.0, 1.0))
+        .seed(6)
+        .build()
+        .unwrap()
+        .render(&world);
+
+    let pixel: f64 = framebuffer.get(2, 2).x();
+    assert!((pixel - 0.5).abs() < 0.03, "{}", pixel);
+}
+
+#[test]
+fn test_invalid_view() {
+    let point = Point3::new(1.0, 2.0, 3.0);
+    let error = Camera::builder().look_from(point).look_at(point).build().err();
+    assert_eq!(error, Some(CameraError::NoViewDirection));
+
+    let error = Camera::builder().look_at(Point3::new(0.0, -5.0, 0.0)).build().err();
+    assert_eq!(error, Some(CameraError::UpParallelToView));
+
+    let error = Camera::builder().vup(Vec3::new(0.0, 0.0, 0.0)).build().err();
+    assert_eq!(error, Some(CameraError::UpParallelToView));
+}
+
+#[test]
+fn test_degenerate_lights() {
+    // Lights without area cannot be hit, and sampling them adds nothing rather than
+    // poisoning pixels with NaN.
+    let lamp = Arc::new(DiffuseLight::new(Color::new(4.0, 4.0, 4.0)));
+    let point: Arc<dyn Hittable> = Arc::new(Sphere::new(Point3::new(0.0, 1.0, -2.0), 0.0, lamp.clone()));
+    let line: Arc<dyn Hittable> = Arc::new(Quad::new(
+        Point3::new(-1.0, 1.0, -2.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        lamp,
+    ));
+    let mut world = test_world();
+    let mut lights = HittableList::new();
+    for light in [point, line] {
+        world.add(Arc::clone(&light));
+        lights.add(light);
+    }
+
+    let framebuffer = Camera::builder()
+        .image_width(4)
+        .samples_per_pixel(16)
+        .background(Color::new(0.0, 0.0, 0.0))
+        .lights(lights)
+        .seed(7)
+        .build()
+        .unwrap()
+        .render(&world);
+
+    for j in 0..4 {
+        for i in 0..4 {
+            assert_eq!(framebuffer.get(i, j), Color::default(), "pixel {}, {}", i, j);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+#[allow(unused_imports)]
+use std::path::PathBuf;
+
+#[allow(unused_imports)]
+use crate::camera::camera::Camera;
+#[allow(unused_imports)]
+use crate::cli::{self, Command, Options};
+#[allow(unused_imports)]
+use crate::output::transform::ToneMap;
+
+#[allow(dead_code)]
+fn parse(args: &[&str]) -> Result<Command, cli::CliError> {
+    cli::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[allow(dead_code)]
+fn parse_options(args: &[&str]) -> Options {
+    match parse(args) {
+        Ok(Command::Render(options)) => options,
+        other => panic!("expected render options, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_no_arguments() {
+    assert_eq!(parse(&[]), Ok(Command::Render(Options::default())));
+}
+
+#[test]
+fn test_help() {
+    assert_eq!(parse(&["--help"]), Ok(Command::Help));
+    assert_eq!(parse(&["-w", "10", "-h"]), Ok(Command::Help));
+}
+
+#[test]
+fn test_flags() {
+    let options = parse_options(&[
+        "--scene", "scenes/three_spheres.scene",
+        "-o", "renders/out.exr",
+        "--width=320",
+        "--aspect", "4:3",
+        "-s", "16",
+        "-d", "8",
+        "--seed", "99",
+        "-j", "2",
+        "--exposure=-0.5",
+        "--tonemap", "reinhard",
+    ]);
+    assert_eq!(options.scene, Some(PathBuf::from("scenes/three_spheres.scene")));
+    assert_eq!(options.output, Some(PathBuf::from("renders/out.exr")));
+    assert_eq!(options.width, Some(320));
+    assert_eq!(options.aspect, Some(4.0 / 3.0));
+    assert_eq!(options.samples, Some(16));
+    assert_eq!(options.depth, Some(8));
+    assert_eq!(options.seed, Some(99));
+    assert_eq!(options.threads, Some(2));
+    assert_eq!(options.exposure, Some(-0.5));
+    assert_eq!(options.tone_map, Some(ToneMap::Reinhard));
+}
+
+#[test]
+fn test_errors() {
+    assert!(parse(&["--width"]).is_err(), "missing value accepted");
+    assert!(parse(&["--width", "wide"]).is_err(), "non-numeric width accepted");
+    assert!(parse(&["--spp", "0"]).is_err(), "zero samples accepted");
+    assert!(parse(&["--frobnicate"]).is_err(), "unknown flag accepted");
+    assert!(parse(&["512"]).is_err(), "positional argument accepted");
+    assert!(parse(&["--height", "100"]).is_err(), "height without width or aspect accepted");
+    assert!(parse(&["-w", "1", "--height", "1", "-a", "1"]).is_err(), "overdetermined size accepted");
+    assert!(parse(&["--tonemap", "filmic"]).is_err(), "unknown tone map accepted");
+}
+
+#[test]
+fn test_apply_to_camera() {
+    let camera: Camera = parse_options(&["-w", "200", "--height", "50", "-s", "3"])
+        .apply_to_camera(Camera::builder().image_width(10).samples_per_pixel(1))
+        .build()
+        .unwrap();
+    assert_eq!(camera.image_width(), 200);
+    assert_eq!(camera.aspect_ratio(), 4.0);
+    assert_eq!(camera.samples_per_pixel(), 3);
+
+    let camera: Camera = parse_options(&["--height", "90", "-a", "16:9"])
+        .apply_to_camera(Camera::builder())
+        .build()
+        .unwrap();
+    assert_eq!(camera.image_width(), 160);
+}
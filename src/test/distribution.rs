@@ -0,0 +1,50 @@
+#[allow(unused_imports)]
+use crate::sampling::distribution::{Distribution1D, Distribution2D};
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+
+#[test]
+fn test_distribution_1d() {
+    let d = Distribution1D::new(vec![1.0, 0.0, 3.0]);
+    assert_eq!(d.total(), 4.0);
+    assert_eq!((d.probability(0), d.probability(1), d.probability(2)), (0.25, 0.0, 0.75));
+    assert_eq!(d.pdf(2), 2.25);
+
+    // Samples land in proportion to the weights, and never in the empty cell.
+    assert_eq!(d.sample(0.0), (0.0, 0));
+    let (x, cell) = d.sample(0.125);
+    assert_eq!(cell, 0);
+    assert!((x - 1.0 / 6.0).abs() < 1e-12);
+    let (x, cell) = d.sample(0.25);
+    assert_eq!(cell, 2);
+    assert!((x - 2.0 / 3.0).abs() < 1e-12);
+    assert!(d.sample(0.999_999_999).0 < 1.0);
+}
+
+#[test]
+fn test_distribution_1d_all_zero() {
+    let d = Distribution1D::new(vec![0.0; 4]);
+    assert_eq!(d.probability(3), 0.25);
+    assert_eq!(d.sample(0.6).1, 2);
+}
+
+#[test]
+fn test_distribution_2d() {
+    // A 2 x 2 grid: top row 1, 1; bottom row 0, 6.
+    let d = Distribution2D::new(&[1.0, 1.0, 0.0, 6.0], 2, 2);
+    assert!((d.pdf([0.25, 0.25]) - 0.5).abs() < 1e-12);
+    assert!((d.pdf([0.75, 0.75]) - 3.0).abs() < 1e-12);
+    assert_eq!(d.pdf([0.25, 0.75]), 0.0);
+
+    let mut rng = Sampler::new(5);
+    let mut bottom_right: usize = 0;
+    for _ in 0..10_000 {
+        let (point, pdf) = d.sample([rng.random_double(), rng.random_double()]);
+        assert_eq!(pdf, d.pdf(point));
+        assert!(pdf > 0.0);
+        if point[0] >= 0.5 && point[1] >= 0.5 {
+            bottom_right += 1;
+        }
+    }
+    assert!((bottom_right as f64 / 10_000.0 - 0.75).abs() < 0.02);
+}
@@ -0,0 +1,133 @@
+#[allow(unused_imports)]
+use std::f64::consts::PI;
+
+#[allow(unused_imports)]
+use crate::environment::constant::ConstantEnvironment;
+#[allow(unused_imports)]
+use crate::environment::environment::Environment;
+#[allow(unused_imports)]
+use crate::environment::gradient::GradientEnvironment;
+#[allow(unused_imports)]
+use crate::environment::hdri::HdriEnvironment;
+#[allow(unused_imports)]
+use crate::environment::sun_sky::SunSkyEnvironment;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Vec3};
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::scene::loader;
+#[allow(unused_imports)]
+use crate::textures::image_texture::ImageTexture;
+
+// An 8 x 4 map, dim everywhere except for one bright texel just above the horizon,
+// straight ahead.
+#[allow(dead_code)]
+fn test_hdri(rotation: f64) -> HdriEnvironment {
+    let mut texels = vec![Color::new(0.1, 0.1, 0.1); 32];
+    texels[8 + 4] = Color::new(100.0, 50.0, 25.0);
+    HdriEnvironment::new(ImageTexture::new(8, 4, texels), rotation, 2.0)
+}
+
+// Estimates the integral of the environment's luminance over the sphere, by uniform
+// sampling and by the environment's own importance sampling.
+#[allow(dead_code)]
+fn integrals(env: &dyn Environment, n: usize) -> (f64, f64) {
+    let luminance = |c: Color| 0.2126 * c.x() + 0.7152 * c.y() + 0.0722 * c.z();
+    let mut rng = Sampler::new(11);
+
+    let mut uniform: f64 = 0.0;
+    let mut importance: f64 = 0.0;
+    for _ in 0..n {
+        uniform += luminance(env.radiance(&Vec3::random_unit_vector(&mut rng))) * 4.0 * PI;
+
+        let d: Vec3 = env.random(&mut rng);
+        assert!((d.length() - 1.0).abs() < 1e-9);
+        let pdf: f64 = env.pdf_value(&d);
+        assert!(pdf > 0.0, "Sampled a direction with zero density");
+        importance += luminance(env.radiance(&d)) / pdf;
+    }
+    (uniform / n as f64, importance / n as f64)
+}
+
+#[allow(dead_code)]
+fn assert_close(a: f64, b: f64, tolerance: f64) {
+    assert!((a - b).abs() <= tolerance * b.abs(), "{} and {} differ", a, b);
+}
+
+#[test]
+fn test_constant_and_gradient() {
+    let constant = ConstantEnvironment::new(Color::new(0.1, 0.2, 0.3));
+    assert_eq!(constant.radiance(&Vec3::new(5.0, -1.0, 2.0)).e, [0.1, 0.2, 0.3]);
+
+    let sky = GradientEnvironment::sky();
+    assert_eq!(sky.radiance(&Vec3::new(0.0, 2.0, 0.0)).e, [0.5, 0.7, 1.0]);
+    assert_eq!(sky.radiance(&Vec3::new(0.0, -2.0, 0.0)).e, [1.0, 1.0, 1.0]);
+    assert_eq!(sky.radiance(&Vec3::new(1.0, 0.0, 0.0)).e, [0.75, 0.85, 1.0]);
+    assert!((sky.pdf_value(&Vec3::new(1.0, 0.0, 0.0)) - 1.0 / (4.0 * PI)).abs() < 1e-12);
+}
+
+#[test]
+fn test_hdri_lookup() {
+    let env = test_hdri(0.0);
+    // The bright texel is in row 1 (polar angles 45 to 90 degrees) and column 4 (just
+    // clockwise of -z seen from above).
+    let ahead = Vec3::new(0.1, 0.3, -1.0);
+    assert_eq!(env.radiance(&ahead).e, [200.0, 100.0, 50.0]);
+    assert_eq!(env.radiance(&Vec3::new(0.1, -0.3, -1.0)).e, [0.2, 0.2, 0.2]);
+    assert_eq!(env.radiance(&Vec3::new(-0.1, 0.3, -1.0)).e, [0.2, 0.2, 0.2]);
+    assert_eq!(env.radiance(&Vec3::new(0.1, 0.3, 1.0)).e, [0.2, 0.2, 0.2]);
+
+    // Turning the map by 180 degrees brings the bright texel round behind.
+    let turned = test_hdri(180.0);
+    assert_eq!(turned.radiance(&Vec3::new(-0.1, 0.3, 1.0)).e, [200.0, 100.0, 50.0]);
+    assert_eq!(turned.radiance(&ahead).e, [0.2, 0.2, 0.2]);
+}
+
+#[test]
+fn test_hdri_importance_sampling() {
+    for rotation in [0.0, 75.0] {
+        let env = test_hdri(rotation);
+        let (uniform, importance) = integrals(&env, 200_000);
+        assert_close(importance, uniform, 0.03);
+
+        // Nearly all samples go to the bright texel.
+        let mut rng = Sampler::new(1);
+        let bright: usize = (0..1000)
+            .filter(|_| env.radiance(&env.random(&mut rng)).x() > 1.0)
+            .count();
+        assert!(bright > 950, "{} samples hit the bright texel", bright);
+    }
+}
+
+#[test]
+fn test_sun_sky() {
+    let sky = SunSkyEnvironment::new(30.0, 90.0, 3.0).sun(30.0, 5.0);
+    let sun = Vec3::new(-(30_f64.to_radians().cos()), 30_f64.to_radians().sin(), 0.0);
+
+    let at_sun: Color = sky.radiance(&sun);
+    let zenith: Color = sky.radiance(&Vec3::new(0.0, 1.0, 0.0));
+    let ground: Color = sky.radiance(&Vec3::new(0.3, -1.0, 0.2));
+    assert!(at_sun.x() > 10.0 * zenith.x());
+    assert!(zenith.z() > zenith.x(), "The clear sky is not blue");
+    assert!((0..3).all(|c| ground[c] >= 0.0 && ground[c] < zenith[c]));
+
+    let (uniform, importance) = integrals(&sky, 200_000);
+    assert_close(importance, uniform, 0.05);
+
+    // A sun below the horizon gives no direct light.
+    let night = SunSkyEnvironment::new(-10.0, 0.0, 3.0);
+    let below = Vec3::new(0.0, -(10_f64.to_radians().sin()), -(10_f64.to_radians().cos()));
+    assert_eq!(night.radiance(&below).e, night.radiance(&Vec3::new(0.0, -0.17, -1.0)).e);
+}
+
+#[test]
+fn test_scene_environments() {
+    let parse = |source: &str| loader::parse(source, None).map(|_| ()).map_err(|err| err.to_string());
+    assert!(parse("environment constant color=0,0,0").is_ok());
+    assert!(parse("environment gradient bottom=1,1,1 top=0.5,0.7,1").is_ok());
+    assert!(parse("environment sky elevation=20 azimuth=45 turbidity=4 sun_size=2 sun_power=3").is_ok());
+    assert_eq!(parse("environment sky"), Err(String::from("line 1: missing property 'elevation'")));
+    assert_eq!(parse("environment stars"), Err(String::from("line 1: unknown environment type 'stars'")));
+    assert!(parse("environment hdri file=missing.exr").unwrap_err().starts_with("line 1: cannot load"));
+}
@@ -0,0 +1,88 @@
+#[allow(unused_imports)]
+use std::path::PathBuf;
+
+#[allow(unused_imports)]
+use crate::geometry::vec3::Color;
+#[allow(unused_imports)]
+use crate::output::framebuffer::Framebuffer;
+#[allow(unused_imports)]
+use crate::output::transform::{OutputTransform, ToneMap, TransferFunction};
+#[allow(unused_imports)]
+use crate::output::writer;
+
+#[allow(dead_code)]
+fn test_framebuffer() -> Framebuffer {
+    let mut fb = Framebuffer::new(3, 2);
+    fb.set(0, 0, Color::new(0.5, 0.25, 0.125));
+    fb.set(2, 1, Color::new(8.0, 16.0, 32.0));
+    fb
+}
+
+#[allow(dead_code)]
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ray_tracing_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn test_get_set() {
+    let fb = test_framebuffer();
+    assert_eq!((fb.width(), fb.height()), (3, 2));
+    assert_eq!(fb.get(2, 1).e, [8.0, 16.0, 32.0]);
+    assert_eq!(fb.get(1, 0).e, [0.0, 0.0, 0.0]);
+}
+
+#[test]
+#[should_panic]
+fn test_out_of_bounds() {
+    let fb = test_framebuffer();
+    let _ = fb.get(3, 0);
+}
+
+#[test]
+fn test_quantized_conversions() {
+    let fb = test_framebuffer();
+    let linear = OutputTransform::new(0.0, ToneMap::None, TransferFunction::Linear);
+
+    let rgb8 = fb.to_rgb8(&linear);
+    assert_eq!(rgb8.get_pixel(0, 0).0, [128, 64, 32]);
+    assert_eq!(rgb8.get_pixel(2, 1).0, [255, 255, 255]);
+
+    let rgb16 = fb.to_rgb16(&linear);
+    assert_eq!(rgb16.get_pixel(0, 0).0, [32768, 16384, 8192]);
+}
+
+#[test]
+fn test_hdr_formats_keep_radiance() {
+    let fb = test_framebuffer();
+    for name in ["radiance.exr", "radiance.hdr"] {
+        let path = temp_path(name);
+        writer::save(&fb, &path, &OutputTransform::default()).unwrap();
+
+        let img = image::open(&path).unwrap().into_rgb32f();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(img.dimensions(), (3, 2));
+
+        let [r, g, b] = img.get_pixel(2, 1).0;
+        assert!((r - 8.0).abs() < 0.1 && (g - 16.0).abs() < 0.2 && (b - 32.0).abs() < 0.4, "{} lost HDR values", name);
+    }
+}
+
+#[test]
+fn test_ldr_formats() {
+    let fb = test_framebuffer();
+    for name in ["ldr.png", "ldr.jpg", "ldr.ppm"] {
+        let path = temp_path(name);
+        writer::save(&fb, &path, &OutputTransform::default()).unwrap();
+
+        let img = image::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!((img.width(), img.height()), (3, 2), "{} has wrong size", name);
+    }
+}
+
+#[test]
+fn test_unknown_extension() {
+    let fb = test_framebuffer();
+    assert!(writer::save(&fb, &temp_path("image.unknown"), &OutputTransform::default()).is_err());
+    assert!(writer::save(&fb, &temp_path("no_extension"), &OutputTransform::default()).is_err());
+}
@@ -0,0 +1,126 @@
+#[allow(unused_imports)]
+use std::fs;
+#[allow(unused_imports)]
+use std::path::PathBuf;
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::mat4::Mat4;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::bvh::BvhNode;
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::hittable_list::HittableList;
+#[allow(unused_imports)]
+use crate::hittables::instance::Instance;
+#[allow(unused_imports)]
+use crate::hittables::mesh::{Mesh, MeshData};
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::scene::error::SceneError;
+#[allow(unused_imports)]
+use crate::scene::loader;
+
+#[allow(dead_code)]
+fn material() -> Arc<dyn Material> {
+    Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)))
+}
+
+// A unit square in the z = 0 plane, facing +z.
+#[allow(dead_code)]
+fn square(mat: Arc<dyn Material>) -> Arc<dyn Hittable> {
+    let positions: Vec<Point3> = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ];
+    let data = MeshData::new(positions, vec![[0, 1, 2], [0, 2, 3]]);
+    Arc::new(Mesh::new(Arc::new(data), mat).unwrap())
+}
+
+#[allow(dead_code)]
+fn hit(object: &dyn Hittable, origin: Point3) -> Option<HitRecord> {
+    let mut rec = HitRecord::default();
+    let r = Ray::new(origin, Vec3::new(0.0, 0.0, -1.0));
+    object.hit(&r, &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(0)).then_some(rec)
+}
+
+#[test]
+fn test_instances_share_geometry() {
+    let own: Arc<dyn Material> = material();
+    let other: Arc<dyn Material> = material();
+    let geometry: Arc<dyn Hittable> = square(Arc::clone(&own));
+
+    let mut world = HittableList::new();
+    for i in 0..10 {
+        let matrix = Mat4::translation(Vec3::new(2.0 * i as f64, 0.0, -1.0));
+        let mut instance = Instance::new(Arc::clone(&geometry), matrix);
+        if i % 2 == 1 {
+            instance = instance.with_material(Arc::clone(&other));
+        }
+        world.add(Arc::new(instance));
+    }
+    assert_eq!(Arc::strong_count(&geometry), 11);
+    let world = BvhNode::new(world);
+
+    let rec = hit(&world, Point3::new(4.5, 0.5, 5.0)).unwrap();
+    assert!((rec.t - 6.0).abs() < 1e-9);
+    assert!(Arc::ptr_eq(rec.mat.as_ref().unwrap(), &own));
+
+    let rec = hit(&world, Point3::new(6.5, 0.5, 5.0)).unwrap();
+    assert!((rec.p.x() - 6.5).abs() < 1e-9 && (rec.p.z() + 1.0).abs() < 1e-9);
+    assert!(Arc::ptr_eq(rec.mat.as_ref().unwrap(), &other));
+
+    assert!(hit(&world, Point3::new(1.5, 0.5, 5.0)).is_none());
+}
+
+#[test]
+fn test_instance_transform() {
+    let instance = Instance::new(square(material()), Mat4::scaling(Vec3::new(3.0, 3.0, 3.0)));
+    assert!(hit(&instance, Point3::new(2.5, 2.5, 1.0)).is_some());
+
+    // Bounding boxes are padded slightly, so compare them loosely.
+    let bbox = instance.bounding_box();
+    assert!((bbox.x.max - 3.0).abs() < 1e-3 && (bbox.y.max - 3.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_scene_model_and_instances() {
+    let dir: PathBuf = std::env::temp_dir().join(format!("ray_tracing_{}_instances", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("tri.obj"), "v 0 0 0\nv 1 0 0\nv 0 1 0\ng a\nf 1 2 3\ng b\nf 1 2 3\n").unwrap();
+    fs::write(dir.join("test.scene"), "
+        material red lambertian albedo=1,0,0
+        model tri file=tri.obj
+        model half file=tri.obj group=b
+        instance tri
+        instance tri material=red translate=2,0,0
+        instance half rotate=0,90,0 scale=2
+    ").unwrap();
+    fs::write(dir.join("bad.scene"), "instance tri").unwrap();
+    fs::write(dir.join("twice.scene"), "model tri file=tri.obj\nmodel tri file=tri.obj").unwrap();
+
+    let scene = loader::load_file(&dir.join("test.scene"), None);
+    let bad = loader::load_file(&dir.join("bad.scene"), None);
+    let twice = loader::load_file(&dir.join("twice.scene"), None);
+    fs::remove_dir_all(&dir).unwrap();
+
+    // Models are not part of the world themselves; each instance is one object.
+    assert_eq!(scene.unwrap().world.objects().len(), 3);
+    assert_eq!(bad.err().unwrap().to_string(), "line 1: undefined model 'tri'");
+    assert_eq!(twice.err().unwrap().to_string(), "line 2: model 'tri' is already defined");
+}
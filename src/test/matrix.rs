@@ -0,0 +1,162 @@
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::mat3::Mat3;
+#[allow(unused_imports)]
+use crate::geometry::mat4::Mat4;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::plane::Plane;
+#[allow(unused_imports)]
+use crate::hittables::quad;
+#[allow(unused_imports)]
+use crate::hittables::sphere::Sphere;
+#[allow(unused_imports)]
+use crate::hittables::transform::Transform;
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::scene::loader;
+
+#[allow(dead_code)]
+fn assert_vec_eq(a: Vec3, b: Vec3) {
+    assert!((a - b).length() < 1e-9, "{:?} != {:?}", a.e, b.e);
+}
+
+#[allow(dead_code)]
+fn material() -> Arc<dyn Material> {
+    Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)))
+}
+
+#[allow(dead_code)]
+fn hit(object: &dyn Hittable, origin: Point3, direction: Vec3) -> Option<HitRecord> {
+    let mut rec = HitRecord::default();
+    object
+        .hit(&Ray::new(origin, direction), &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(0))
+        .then_some(rec)
+}
+
+#[test]
+fn test_mat3() {
+    let m = Mat3::new([[2.0, 0.0, 1.0], [1.0, 3.0, 0.0], [0.0, 1.0, 4.0]]);
+    assert_eq!(m.determinant(), 25.0);
+    assert_eq!(m.transpose().m[0], [2.0, 1.0, 0.0]);
+    assert_vec_eq(m * Vec3::new(1.0, 2.0, 3.0), Vec3::new(5.0, 7.0, 14.0));
+
+    let product: Mat3 = m * m.inverse().unwrap();
+    for i in 0..3 {
+        assert_vec_eq(Vec3 { e: product.m[i] }, Vec3 { e: Mat3::IDENTITY.m[i] });
+    }
+    assert!(Mat3::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 0.0, 1.0]]).inverse().is_none());
+}
+
+#[test]
+fn test_mat4_transforms() {
+    let p = Point3::new(1.0, 2.0, 3.0);
+    assert_vec_eq(Mat4::translation(Vec3::new(1.0, -1.0, 0.5)).transform_point(&p), Point3::new(2.0, 1.0, 3.5));
+    assert_vec_eq(Mat4::translation(Vec3::new(1.0, -1.0, 0.5)).transform_vector(&p), p);
+    assert_vec_eq(Mat4::scaling(Vec3::new(2.0, 3.0, -1.0)).transform_point(&p), Point3::new(2.0, 6.0, -3.0));
+
+    // Right-handed rotations.
+    assert_vec_eq(Mat4::rotation_x(90.0).transform_vector(&Vec3::new(0.0, 1.0, 0.0)), Vec3::new(0.0, 0.0, 1.0));
+    assert_vec_eq(Mat4::rotation_y(90.0).transform_vector(&Vec3::new(0.0, 0.0, 1.0)), Vec3::new(1.0, 0.0, 0.0));
+    assert_vec_eq(Mat4::rotation_z(90.0).transform_vector(&Vec3::new(1.0, 0.0, 0.0)), Vec3::new(0.0, 1.0, 0.0));
+    let axis = Vec3::new(1.0, 1.0, 1.0);
+    assert_vec_eq(Mat4::rotation(axis, 120.0).transform_vector(&Vec3::new(1.0, 0.0, 0.0)), Vec3::new(0.0, 1.0, 0.0));
+
+    // Composition applies the right-hand matrix first.
+    let m: Mat4 = Mat4::translation(Vec3::new(10.0, 0.0, 0.0)) * Mat4::scaling(Vec3::new(2.0, 2.0, 2.0));
+    assert_vec_eq(m.transform_point(&p), Point3::new(12.0, 4.0, 6.0));
+
+    let inverse: Mat4 = (m * Mat4::rotation(axis, 33.0)).inverse().unwrap();
+    assert_vec_eq(inverse.transform_point(&(m * Mat4::rotation(axis, 33.0)).transform_point(&p)), p);
+    assert!(Mat4::scaling(Vec3::new(1.0, 0.0, 1.0)).inverse().is_none());
+    assert_eq!(Mat4::IDENTITY.transpose(), Mat4::IDENTITY);
+}
+
+#[test]
+fn test_transformed_sphere() {
+    // A unit sphere stretched to an ellipsoid with radii 2, 1, 1, then moved to x = 5.
+    let sphere: Arc<dyn Hittable> = Arc::new(Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, material()));
+    let matrix: Mat4 = Mat4::translation(Vec3::new(5.0, 0.0, 0.0)) * Mat4::scaling(Vec3::new(2.0, 1.0, 1.0));
+    let ellipsoid = Transform::new(sphere, matrix);
+
+    let rec = hit(&ellipsoid, Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0)).unwrap();
+    assert!((rec.t - 3.0).abs() < 1e-9);
+    assert_vec_eq(rec.p, Point3::new(3.0, 0.0, 0.0));
+    assert_vec_eq(rec.normal, Vec3::new(-1.0, 0.0, 0.0));
+    assert!(rec.front_face);
+
+    // Off-axis, the normal is still perpendicular to the stretched surface x²/4 + y² + z² = 1.
+    let rec = hit(&ellipsoid, Point3::new(5.0 + 2.0_f64.sqrt(), 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).unwrap();
+    let local: Vec3 = rec.p - Point3::new(5.0, 0.0, 0.0);
+    let gradient: Vec3 = Vec3::unit_vector(Vec3::new(local.x() / 4.0, local.y(), local.z()));
+    assert_vec_eq(rec.normal, gradient);
+
+    // Bounding boxes are padded slightly, so compare them loosely.
+    let bbox = ellipsoid.bounding_box();
+    assert!((bbox.x.min - 3.0).abs() < 1e-3 && (bbox.x.max - 7.0).abs() < 1e-3);
+    assert!((bbox.y.max - 1.0).abs() < 1e-3);
+
+    assert!(hit(&ellipsoid, Point3::new(0.0, 1.5, 0.0), Vec3::new(1.0, 0.0, 0.0)).is_none());
+}
+
+#[test]
+fn test_rotated_box() {
+    let cube: Arc<dyn Hittable> = Arc::new(quad::make_box(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0), material()));
+    let rotated = Transform::translate(Arc::new(Transform::rotate_y(cube, 45.0)), Vec3::new(0.0, 0.0, -5.0));
+
+    // The corner at (1, y, 1) swings round onto the +x axis, at distance √2.
+    let rec = hit(&rotated, Point3::new(5.0, 0.5, -5.0), Vec3::new(-1.0, 0.0, 0.0)).unwrap();
+    assert!((rec.p.x() - 2.0_f64.sqrt()).abs() < 1e-6);
+    assert!(rec.front_face);
+
+    let bbox = rotated.bounding_box();
+    assert!((bbox.x.max - 2.0_f64.sqrt()).abs() < 1e-3 && bbox.x.min.abs() < 1e-3);
+}
+
+#[test]
+fn test_transformed_plane_is_unbounded() {
+    let plane: Arc<dyn Hittable> = Arc::new(Plane::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), material()));
+    let tilted = Transform::new(plane, Mat4::rotation_z(30.0));
+    assert!(!tilted.bounding_box().is_bounded());
+    assert!(hit(&tilted, Point3::new(0.0, 10.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).is_some());
+}
+
+#[test]
+fn test_scene_transforms() {
+    let parse = |source: &str| loader::parse(source, None).map(|_| ()).map_err(|err| err.to_string());
+    let prefix = "material m lambertian albedo=1,1,1\n";
+
+    assert!(parse(&format!("{}sphere center=0,0,0 radius=1 material=m scale=2 rotate=0,45,0 translate=1,2,3", prefix)).is_ok());
+    assert!(parse(&format!("{}sphere center=0,0,0 radius=1 material=m scale=1,2,3", prefix)).is_ok());
+    assert!(parse(&format!("{}box min=0,0,0 max=1,1,1 material=m matrix=1,0,0,1,0,1,0,2,0,0,1,3", prefix)).is_ok());
+    assert_eq!(
+        parse(&format!("{}sphere center=0,0,0 radius=1 material=m scale=0", prefix)),
+        Err(String::from("line 2: the transform is singular")),
+    );
+    assert_eq!(
+        parse(&format!("{}sphere center=0,0,0 radius=1 material=m matrix=1,0,0,0", prefix)),
+        Err(String::from("line 2: invalid matrix '1,0,0,0', expected 12 or 16 values")),
+    );
+    assert_eq!(
+        parse(&format!("{}sphere center=0,0,0 radius=1 material=m matrix=1,0,0,0,0,1,0,0,0,0,1,0,1,0,0,1", prefix)),
+        Err(String::from("line 2: the transform is not affine")),
+    );
+    assert_eq!(
+        parse(&format!("{}sphere center=0,0,0 radius=1 material=m matrix=1,0,0,0,0,1,0,0,0,0,1,0 scale=2", prefix)),
+        Err(String::from("line 2: 'matrix' cannot be combined with 'scale', 'rotate' or 'translate'")),
+    );
+}
@@ -0,0 +1,117 @@
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::constant_medium::ConstantMedium;
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::sphere::Sphere;
+#[allow(unused_imports)]
+use crate::materials::isotropic::Isotropic;
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::scene::loader;
+
+#[allow(dead_code)]
+fn unit_sphere_medium(density: f64) -> ConstantMedium {
+    let boundary: Arc<dyn Hittable> = Arc::new(Sphere::new(
+        Point3::new(0.0, 0.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    ));
+    ConstantMedium::new(boundary, density, Color::new(0.8, 0.8, 0.8))
+}
+
+#[test]
+fn test_transmittance() {
+    // Along a diameter, a ray passes through with probability exp(-density * 2).
+    let medium = unit_sphere_medium(0.5);
+    let mut rng = Sampler::new(1);
+    let r = Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -2.0));
+    let t = Interval::new(0.001, f64::INFINITY);
+
+    let trials = 20000;
+    let mut passed = 0;
+    for _ in 0..trials {
+        let mut rec = HitRecord::default();
+        if medium.hit(&r, &t, &mut rec, &mut rng) {
+            // Scattering happens inside the boundary, and uses the phase function.
+            assert!(rec.p.length() <= 1.0 + 1e-9);
+            assert!((rec.p - r.at(rec.t)).length() < 1e-12);
+        } else {
+            passed += 1;
+        }
+    }
+    let fraction: f64 = passed as f64 / trials as f64;
+    assert!((fraction - (-1.0_f64).exp()).abs() < 0.015, "{}", fraction);
+}
+
+#[test]
+fn test_ray_starting_inside() {
+    // A dense medium scatters a ray starting at its center almost immediately.
+    let medium = unit_sphere_medium(1000.0);
+    let mut rng = Sampler::new(2);
+    let mut rec = HitRecord::default();
+    let r = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+    assert!(medium.hit(&r, &Interval::new(0.001, f64::INFINITY), &mut rec, &mut rng));
+    assert!(rec.t >= 0.001 && rec.t < 0.1);
+
+    // Rays that miss the boundary never scatter.
+    let miss = Ray::new(Point3::new(0.0, 2.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+    assert!(!medium.hit(&miss, &Interval::new(0.001, f64::INFINITY), &mut rec, &mut rng));
+}
+
+#[test]
+fn test_isotropic_scatter() {
+    let mut rng = Sampler::new(4);
+    let material = Isotropic::new(Color::new(0.25, 0.5, 0.75));
+    let rec = HitRecord { p: Point3::new(1.0, 2.0, 3.0), ..HitRecord::default() };
+    let r_in = Ray::with_time(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), 0.5);
+
+    // Directions are spread evenly over the sphere, so they average out to nothing.
+    let mut sum = Vec3::default();
+    for _ in 0..10000 {
+        let mut scattered = Ray::default();
+        let mut attenuation = Color::default();
+        assert!(material.scatter(&r_in, &rec, &mut attenuation, &mut scattered, &mut rng));
+        assert_eq!(attenuation.e, [0.25, 0.5, 0.75]);
+        assert_eq!(scattered.time, 0.5);
+        assert!((scattered.direction.length() - 1.0).abs() < 1e-9);
+        sum += scattered.direction;
+    }
+    assert!(sum.length() / 10000.0 < 0.03);
+}
+
+#[test]
+fn test_scene_media() {
+    let parse = |source: &str| loader::parse(source, None).map(|_| ()).map_err(|err| err.to_string());
+
+    assert!(parse("fog density=0.1 albedo=0.9,0.9,0.9").is_ok());
+    assert_eq!(parse("fog density=-1"), Err(String::from("line 1: 'density' must not be negative")));
+    assert!(parse("material smoke isotropic albedo=0.2,0.2,0.2\nbox min=0,0,0 max=1,1,1 material=smoke density=0.5 rotate=0,10,0").is_ok());
+    assert_eq!(
+        parse("material smoke isotropic albedo=1,1,1\nsphere center=0,0,0 radius=1 material=smoke density=0"),
+        Err(String::from("line 2: 'density' must be positive")),
+    );
+    assert_eq!(
+        parse("material smoke isotropic albedo=1,1,1\nsphere center=0,0,0 radius=1 material=smoke density=nan"),
+        Err(String::from("line 2: invalid value 'nan' for 'density'")),
+    );
+    // Open surfaces have no inside to fill.
+    assert_eq!(
+        parse("material smoke isotropic albedo=1,1,1\nquad q=0,0,0 u=1,0,0 v=0,1,0 material=smoke density=1"),
+        Err(String::from("line 2: 'density' requires a closed shape, a sphere or a box")),
+    );
+}
@@ -1 +1,23 @@
-mod vec3;
\ No newline at end of file
+mod bvh;
+mod camera;
+mod cli;
+mod distribution;
+mod environment;
+mod framebuffer;
+mod instance;
+mod matrix;
+mod medium;
+mod motion;
+mod obj;
+mod pdf;
+mod planar;
+mod sampler;
+mod scene;
+mod texture;
+mod transform;
+mod triangle;
+// Indexing is tested through plain assignment as well as the compound operators.
+#[allow(clippy::assign_op_pattern)]
+mod vec3;
+mod volume;
+mod warp;
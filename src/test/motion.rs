@@ -0,0 +1,144 @@
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::quad;
+#[allow(unused_imports)]
+use crate::hittables::sphere::Sphere;
+#[allow(unused_imports)]
+use crate::hittables::transform::{MovingTransform, Placement};
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::materials::metal::Metal;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::scene::loader;
+
+#[allow(dead_code)]
+fn material() -> Arc<dyn Material> {
+    Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)))
+}
+
+#[allow(dead_code)]
+fn hit(object: &dyn Hittable, r: &Ray) -> Option<HitRecord> {
+    let mut rec = HitRecord::default();
+    object.hit(r, &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(0)).then_some(rec)
+}
+
+#[test]
+fn test_scattered_rays_keep_their_time() {
+    let mut rng = Sampler::new(1);
+    let sphere = Sphere::new(Point3::new(0.0, 0.0, -2.0), 1.0, material());
+    let r = Ray::with_time(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0), 0.25);
+    let rec = hit(&sphere, &r).unwrap();
+
+    for mat in [material(), Arc::new(Metal::new(Color::new(1.0, 1.0, 1.0), 0.0)) as Arc<dyn Material>] {
+        let mut scattered = Ray::default();
+        let mut attenuation = Color::default();
+        assert!(mat.scatter(&r, &rec, &mut attenuation, &mut scattered, &mut rng));
+        assert_eq!(scattered.time, 0.25);
+    }
+}
+
+#[test]
+fn test_moving_sphere() {
+    let sphere = Sphere::moving(Point3::new(0.0, 0.0, -5.0), Point3::new(4.0, 0.0, -5.0), 1.0, material());
+    let along = |x: f64, time: f64| Ray::with_time(Point3::new(x, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0), time);
+
+    assert!(hit(&sphere, &along(0.0, 0.0)).is_some());
+    assert!(hit(&sphere, &along(0.0, 1.0)).is_none());
+    assert!(hit(&sphere, &along(4.0, 1.0)).is_some());
+
+    // Halfway through, the normal is taken about the center at that time.
+    let rec = hit(&sphere, &along(2.0, 0.5)).unwrap();
+    assert!((rec.t - 4.0).abs() < 1e-9);
+    assert!((rec.normal - Vec3::new(0.0, 0.0, 1.0)).length() < 1e-9);
+
+    // Outside the unit interval the sphere rests at the ends of its path.
+    assert!(hit(&sphere, &along(4.0, 3.0)).is_some());
+    assert!(hit(&sphere, &along(0.0, -1.0)).is_some());
+
+    let bbox = sphere.bounding_box();
+    assert!((bbox.x.min + 1.0).abs() < 1e-3 && (bbox.x.max - 5.0).abs() < 1e-3);
+}
+
+#[test]
+#[should_panic]
+fn test_negative_radius() {
+    let _ = Sphere::moving(Point3::new(0.0, 0.0, -5.0), Point3::new(4.0, 0.0, -5.0), -1.0, material());
+}
+
+#[test]
+fn test_placement() {
+    let placement = Placement {
+        scale: Vec3::new(2.0, 1.0, 0.5),
+        rotate: Vec3::new(10.0, 20.0, 30.0),
+        translate: Vec3::new(1.0, 2.0, 3.0),
+    };
+    let p = Point3::new(0.3, -0.7, 1.1);
+    let q: Point3 = placement.inverse_matrix().transform_point(&placement.matrix().transform_point(&p));
+    assert!((q - p).length() < 1e-9);
+
+    let halfway: Placement = Placement::default().lerp(&placement, 0.5);
+    assert!((halfway.scale - Vec3::new(1.5, 1.0, 0.75)).length() < 1e-12);
+    assert!((halfway.rotate - Vec3::new(5.0, 10.0, 15.0)).length() < 1e-12);
+}
+
+#[test]
+fn test_moving_transform() {
+    let cube: Arc<dyn Hittable> = Arc::new(quad::make_box(Point3::new(-0.5, -0.5, -0.5), Point3::new(0.5, 0.5, 0.5), material()));
+    let start = Placement { translate: Vec3::new(0.0, 0.0, -5.0), ..Placement::default() };
+    let end = Placement { rotate: Vec3::new(0.0, 90.0, 0.0), translate: Vec3::new(3.0, 0.0, -5.0), ..Placement::default() };
+    let moving = MovingTransform::new(cube, start, end);
+
+    let down = |time: f64| Ray::with_time(Point3::new(1.5, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0), time);
+    assert!(hit(&moving, &down(0.0)).is_none());
+    assert!(hit(&moving, &down(1.0)).is_none());
+
+    // Halfway, the cube is at x = 1.5 and turned 45 degrees, so an edge faces the ray.
+    let rec = hit(&moving, &down(0.5)).unwrap();
+    assert!((rec.p.z() - (-5.0 + 0.5_f64.sqrt())).abs() < 1e-9);
+    assert!(rec.normal.z() > 0.0);
+
+    // Every hit over the motion lies inside the bounding box.
+    let bbox = moving.bounding_box();
+    let mut rng = Sampler::new(5);
+    for _ in 0..1000 {
+        let time: f64 = rng.random_double();
+        let origin = Point3::new(rng.random_double_range(-1.0, 4.0), rng.random_double_range(-1.0, 1.0), 0.0);
+        if let Some(rec) = hit(&moving, &Ray::with_time(origin, Vec3::new(0.0, 0.0, -1.0), time)) {
+            assert!(bbox.x.contains(rec.p.x()) && bbox.y.contains(rec.p.y()) && bbox.z.contains(rec.p.z()));
+        }
+    }
+}
+
+#[test]
+fn test_scene_motion() {
+    let parse = |source: &str| loader::parse(source, None).map(|_| ()).map_err(|err| err.to_string());
+    let prefix = "material m lambertian albedo=1,1,1\n";
+
+    assert!(parse("camera shutter=0,1").is_ok());
+    assert_eq!(parse("camera shutter=1,0"), Err(String::from("line 1: invalid shutter '1,0', expected open,close")));
+    assert!(parse(&format!("{}sphere center=0,0,0 center_end=1,0,0 radius=1 material=m", prefix)).is_ok());
+    assert!(parse(&format!("{}box min=0,0,0 max=1,1,1 material=m translate=1,0,0 rotate_end=0,90,0", prefix)).is_ok());
+    assert_eq!(
+        parse(&format!("{}sphere center=0,0,0 radius=1 material=m scale=1 scale_end=-1", prefix)),
+        Err(String::from("line 2: a moving scale cannot change sign")),
+    );
+    assert_eq!(
+        parse(&format!("{}sphere center=0,0,0 radius=1 material=m matrix=1,0,0,0,0,1,0,0,0,0,1,0 translate_end=1,0,0", prefix)),
+        Err(String::from("line 2: 'matrix' cannot be combined with 'scale', 'rotate' or 'translate'")),
+    );
+}
@@ -0,0 +1,202 @@
+#[allow(unused_imports)]
+use std::fs;
+#[allow(unused_imports)]
+use std::path::{Path, PathBuf};
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::scene::error::SceneError;
+#[allow(unused_imports)]
+use crate::scene::obj::{self, ObjModel};
+#[allow(unused_imports)]
+use crate::scene::{loader, mtl};
+
+#[allow(dead_code)]
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ray_tracing_{}_{}", std::process::id(), name));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[allow(dead_code)]
+fn parse(source: &str) -> ObjModel {
+    obj::parse(source, Path::new("")).unwrap()
+}
+
+#[allow(dead_code)]
+fn parse_error(source: &str) -> (usize, String) {
+    match obj::parse(source, Path::new("")) {
+        Err(SceneError::Parse { line, message }) => (line, message),
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("model parsed without error"),
+    }
+}
+
+// The attenuation a material applies to a ray hitting a surface head on.
+#[allow(dead_code)]
+fn attenuation(material: &Arc<dyn Material>) -> Color {
+    let r = Ray::new(Point3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    let mut rec = HitRecord::default();
+    rec.t = 1.0;
+    rec.set_face_normal(&r, &Vec3::new(0.0, 0.0, 1.0));
+
+    let mut attenuation = Color::default();
+    let mut scattered = Ray::default();
+    material.scatter(&r, &rec, &mut attenuation, &mut scattered, &mut Sampler::new(0));
+    attenuation
+}
+
+#[test]
+fn test_quad_is_triangulated() {
+    let model = parse("
+        v 0 0 0
+        v 1 0 0
+        v 1 1 0
+        v 0 1 0
+        vt 0 0
+        vt 1 0
+        vt 1 1
+        vt 0 1
+        vn 0 0 1
+        f 1/1/1 2/2/1 3/3/1 4/4/1
+    ");
+
+    assert_eq!(model.groups.len(), 1);
+    let data = &model.groups[0].data;
+    assert_eq!(data.indices, vec![[0, 1, 2], [0, 2, 3]]);
+    assert_eq!(data.positions.len(), 4);
+    assert_eq!(data.uvs.as_ref().unwrap()[2], [1.0, 1.0]);
+    assert_eq!(data.normals.as_ref().unwrap().len(), 4);
+
+    let fallback: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    let world = model.groups[0].to_mesh(&fallback).unwrap();
+    let mut rec = HitRecord::default();
+    let r = Ray::new(Point3::new(0.25, 0.75, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    assert!(world.hit(&r, &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(0)));
+    assert!((rec.u - 0.25).abs() < 1e-12 && (rec.v - 0.75).abs() < 1e-12);
+}
+
+#[test]
+fn test_negative_indices_and_partial_attributes() {
+    let model = parse("
+        v 0 0 0
+        v 1 0 0
+        v 0 1 0
+        vn 0 0 1
+        f -3//1 -2//1 -1//1
+        f 1 2 3
+    ");
+
+    // The second face has no normals, so the group drops them, and the corners of both
+    // faces collapse onto the same three vertices.
+    let data = &model.groups[0].data;
+    assert!(data.normals.is_none() && data.uvs.is_none());
+    assert_eq!(data.indices, vec![[0, 1, 2], [0, 1, 2]]);
+}
+
+#[test]
+fn test_groups_and_materials() {
+    let dir = temp_dir("obj_groups");
+    fs::write(dir.join("scene.mtl"), "
+        newmtl red
+        Kd 0.8 0.1 0.1
+        newmtl chrome
+        illum 3
+        Ks 0.9 0.9 0.9
+        Ns 1000
+        newmtl glass
+        d 0.1
+        Ni 1.5
+    ").unwrap();
+    fs::write(dir.join("scene.obj"), "
+        mtllib scene.mtl
+        v 0 0 0
+        v 1 0 0
+        v 0 1 0
+        g left
+        usemtl red
+        f 1 2 3
+        usemtl chrome
+        f 1 2 3
+        g right
+        usemtl glass
+        f 1 2 3
+        usemtl missing
+        f 1 2 3
+    ").unwrap();
+
+    let model = obj::load_file(&dir.join("scene.obj")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let names: Vec<&str> = model.groups.iter().map(|group| group.name.as_str()).collect();
+    assert_eq!(names, ["left", "left", "right", "right"]);
+    assert_eq!(attenuation(model.groups[0].material.as_ref().unwrap()).e, [0.8, 0.1, 0.1]);
+    assert_eq!(attenuation(model.groups[1].material.as_ref().unwrap()).e, [0.9, 0.9, 0.9]);
+    assert_eq!(attenuation(model.groups[2].material.as_ref().unwrap()).e, [1.0, 1.0, 1.0]);
+    assert!(model.groups[3].material.is_none());
+}
+
+#[test]
+fn test_mtl_defaults() {
+    let library = mtl::parse("Kd 1 0 0\nnewmtl plain\nillum 2\nnewmtl gray\nKd 0.25", Path::new("")).unwrap();
+    assert_eq!(library.len(), 2);
+    assert_eq!(attenuation(&library["plain"]).e, [0.8, 0.8, 0.8]);
+    assert_eq!(attenuation(&library["gray"]).e, [0.25, 0.25, 0.25]);
+}
+
+#[test]
+fn test_errors_report_line_numbers() {
+    assert_eq!(parse_error("v 0 0 0\nv 1 0\n"), (2, String::from("'v' takes 3 to 4 values")));
+    assert_eq!(parse_error("v 0 0 x"), (1, String::from("invalid number 'x'")));
+    assert_eq!(parse_error("v 0 0 0\n\nf 1 2"), (3, String::from("a face needs at least three vertices")));
+    assert_eq!(parse_error("v 0 0 0\nf 1 1 4"), (2, String::from("face vertex '4' is out of range")));
+    assert_eq!(parse_error("v 0 0 0\nf 1 1 -2"), (2, String::from("face vertex '-2' is out of range")));
+    assert_eq!(parse_error("v 0 0 0\nf 1 1 0"), (2, String::from("face vertex '0' is out of range")));
+    assert_eq!(parse_error("v 0 0 0\nf 1 1 1/1"), (2, String::from("face vertex '1/1' is out of range")));
+    assert_eq!(parse_error("mtllib missing.mtl").0, 1);
+
+    match mtl::parse("newmtl a\nKd 1 0", Path::new("")) {
+        Err(SceneError::Parse { line, message }) => assert_eq!((line, message.as_str()), (2, "invalid color '1 0' for 'Kd'")),
+        _ => panic!("expected a parse error"),
+    }
+}
+
+#[test]
+fn test_scene_mesh_directive() {
+    let dir = temp_dir("obj_scene");
+    fs::write(dir.join("tri.obj"), "v 0 0 0\nv 1 0 0\nv 0 1 0\ng a\nf 1 2 3\ng b\nf 1 2 3\n").unwrap();
+    fs::write(dir.join("test.scene"), "
+        material red lambertian albedo=1,0,0
+        mesh file=tri.obj
+        mesh file=tri.obj group=b material=red
+    ").unwrap();
+    fs::write(dir.join("bad.scene"), "mesh file=tri.obj group=c").unwrap();
+
+    let scene = loader::load_file(&dir.join("test.scene"), None);
+    let bad = loader::load_file(&dir.join("bad.scene"), None);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(scene.unwrap().world.objects().len(), 3);
+    match bad {
+        Err(SceneError::Parse { line, message }) => {
+            assert_eq!(line, 1);
+            assert!(message.ends_with("has no group 'c'"), "{}", message);
+        }
+        _ => panic!("expected a parse error"),
+    }
+}
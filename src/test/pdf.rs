@@ -0,0 +1,175 @@
+#[allow(unused_imports)]
+use std::f64::consts::PI;
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::mat4::Mat4;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::disk::Disk;
+#[allow(unused_imports)]
+use crate::hittables::hittable::Hittable;
+#[allow(unused_imports)]
+use crate::hittables::hittable_list::HittableList;
+#[allow(unused_imports)]
+use crate::hittables::quad::Quad;
+#[allow(unused_imports)]
+use crate::hittables::sphere::Sphere;
+#[allow(unused_imports)]
+use crate::hittables::transform::Transform;
+#[allow(unused_imports)]
+use crate::hittables::triangle::Triangle;
+#[allow(unused_imports)]
+use crate::materials::diffuse_light::DiffuseLight;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::sampling::pdf::{power_heuristic, CosinePdf, HittablePdf, MixturePdf, Pdf, SpherePdf};
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+
+#[allow(dead_code)]
+fn lamp() -> Arc<dyn Material> {
+    Arc::new(DiffuseLight::new(Color::new(1.0, 1.0, 1.0)))
+}
+
+// Estimates the integral of the density over all directions, which must be one. The
+// directions are stratified in height and azimuth, which are both uniform over the sphere,
+// so small lights are found evenly whatever the seed.
+#[allow(dead_code)]
+fn integral(pdf: &dyn Pdf, rng: &mut Sampler) -> f64 {
+    let n = 450;
+    let mut sum: f64 = 0.0;
+    for i in 0..n * n {
+        let z: f64 = 1.0 - 2.0 * ((i % n) as f64 + rng.random_double()) / n as f64;
+        let phi: f64 = 2.0 * PI * ((i / n) as f64 + rng.random_double()) / n as f64;
+        let r: f64 = (1.0 - z * z).max(0.0).sqrt();
+        sum += pdf.value(&Vec3::new(r * phi.cos(), r * phi.sin(), z));
+    }
+    4.0 * PI * sum / (n * n) as f64
+}
+
+// Estimates the solid angle of the directions `generate` can reach, as the mean of the
+// reciprocal densities of its samples.
+#[allow(dead_code)]
+fn support(pdf: &dyn Pdf, rng: &mut Sampler) -> f64 {
+    let n = 20000;
+    let mut sum: f64 = 0.0;
+    for _ in 0..n {
+        let value: f64 = pdf.value(&pdf.generate(rng));
+        assert!(value > 0.0);
+        sum += 1.0 / value;
+    }
+    sum / n as f64
+}
+
+#[test]
+fn test_pdfs_integrate_to_one() {
+    let mut rng = Sampler::new(1);
+    let origin = Point3::new(0.0, 0.0, 0.0);
+    // Subtends a cone of half angle 30 degrees.
+    let sphere = Sphere::new(Point3::new(0.0, 0.0, -2.0), 1.0, lamp());
+    // A square of side 2 at distance 1.
+    let quad = Quad::new(Point3::new(-1.0, -1.0, 1.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0), lamp());
+    // Half of that square, cut along its diagonal.
+    let triangle = Triangle::new(Point3::new(-1.0, -1.0, 1.0), Point3::new(1.0, -1.0, 1.0), Point3::new(-1.0, 1.0, 1.0), lamp());
+    // Subtends a cone of half angle 45 degrees.
+    let disk = Disk::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 1.0, lamp());
+    let cosine = CosinePdf::new(&Vec3::new(1.0, 2.0, 3.0));
+
+    let pdfs: [(&dyn Pdf, f64); 7] = [
+        (&SpherePdf, 4.0 * PI),
+        (&cosine, 2.0 * PI),
+        (&HittablePdf::new(&sphere, origin, 0.0), 2.0 * PI * (1.0 - 3_f64.sqrt() / 2.0)),
+        (&HittablePdf::new(&quad, origin, 0.0), 2.0 * PI / 3.0),
+        (&HittablePdf::new(&triangle, origin, 0.0), PI / 3.0),
+        (&HittablePdf::new(&disk, origin, 0.0), 2.0 * PI * (1.0 - 0.5_f64.sqrt())),
+        (&MixturePdf::new(CosinePdf::new(&Vec3::new(0.0, 1.0, 0.0)), SpherePdf, 0.25), 4.0 * PI),
+    ];
+    for (pdf, solid_angle) in pdfs {
+        let total: f64 = integral(pdf, &mut rng);
+        assert!((total - 1.0).abs() < 0.02, "{}", total);
+        let reached: f64 = support(pdf, &mut rng);
+        assert!((reached - solid_angle).abs() < 0.02 * solid_angle, "{} != {}", reached, solid_angle);
+    }
+}
+
+#[test]
+fn test_sphere_light() {
+    let mut rng = Sampler::new(2);
+    let sphere = Sphere::moving(Point3::new(0.0, 0.0, 0.0), Point3::new(10.0, 0.0, 0.0), 1.0, lamp());
+
+    // From inside, all directions are equally likely.
+    let inside = Ray::with_time(Point3::new(0.5, 0.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+    assert_eq!(sphere.pdf_value(&inside), 1.0 / (4.0 * PI));
+
+    // Directions follow the sphere along its path.
+    let origin = Point3::new(10.0, 5.0, 0.0);
+    for _ in 0..100 {
+        let direction: Vec3 = sphere.random(&origin, 1.0, &mut rng);
+        assert!(direction.y() < 0.0 && direction.x().abs() < 0.25 * direction.length());
+        assert!(sphere.pdf_value(&Ray::with_time(origin, direction, 1.0)) > 0.0);
+        assert_eq!(sphere.pdf_value(&Ray::with_time(origin, direction, 0.0)), 0.0);
+    }
+
+    // A distant sphere keeps an accurate solid angle, close to pi r^2 / d^2.
+    let distant = Ray::new(Point3::new(0.0, 0.0, 1e6), Vec3::new(0.0, 0.0, -1.0));
+    let solid_angle: f64 = 1.0 / sphere.pdf_value(&distant);
+    assert!((solid_angle / (PI * 1e-12) - 1.0).abs() < 1e-9, "{}", solid_angle);
+}
+
+#[test]
+fn test_transformed_light() {
+    // A unit square placed by a transform that stretches it unevenly has the same density
+    // over directions as the equivalent quad built in place.
+    let matrix: Mat4 = Mat4::translation(Vec3::new(1.0, 2.0, -3.0))
+        * Mat4::rotation(Vec3::new(1.0, 1.0, 0.0), 30.0)
+        * Mat4::scaling(Vec3::new(2.0, 3.0, 0.5));
+    let unit = Quad::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), lamp());
+    let placed = Transform::new(Arc::new(unit), matrix);
+    let direct = Quad::new(
+        matrix.transform_point(&Point3::new(0.0, 0.0, 0.0)),
+        matrix.transform_vector(&Vec3::new(1.0, 0.0, 0.0)),
+        matrix.transform_vector(&Vec3::new(0.0, 1.0, 0.0)),
+        lamp(),
+    );
+
+    let mut rng = Sampler::new(3);
+    let origin = Point3::new(-2.0, 1.0, 4.0);
+    for _ in 0..100 {
+        let direction: Vec3 = placed.random(&origin, 0.0, &mut rng);
+        let expected: f64 = direct.pdf_value(&Ray::new(origin, direction));
+        let value: f64 = placed.pdf_value(&Ray::new(origin, 3.0 * direction));
+        assert!(expected > 0.0 && (value / expected - 1.0).abs() < 1e-9, "{} != {}", value, expected);
+    }
+
+    // A sphere stretched into an ellipsoid still has a normalized density.
+    let ellipsoid = Transform::new(Arc::new(Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, lamp())), matrix);
+    let total: f64 = integral(&HittablePdf::new(&ellipsoid, origin, 0.0), &mut rng);
+    assert!((total - 1.0).abs() < 0.02, "{}", total);
+}
+
+#[test]
+fn test_list_pdf() {
+    let mut lights = HittableList::new();
+    let near: Arc<dyn Hittable> = Arc::new(Sphere::new(Point3::new(0.0, 0.0, -2.0), 1.0, lamp()));
+    let far: Arc<dyn Hittable> = Arc::new(Sphere::new(Point3::new(0.0, 0.0, -4.0), 1.0, lamp()));
+    lights.add(Arc::clone(&near));
+    lights.add(Arc::clone(&far));
+
+    let r = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+    assert!((lights.pdf_value(&r) - 0.5 * (near.pdf_value(&r) + far.pdf_value(&r))).abs() < 1e-12);
+    assert_eq!(HittableList::new().pdf_value(&r), 0.0);
+}
+
+#[test]
+fn test_power_heuristic() {
+    assert_eq!(power_heuristic(1.0, 1.0), 0.5);
+    assert_eq!(power_heuristic(2.0, 0.0), 1.0);
+    assert_eq!(power_heuristic(0.0, 0.0), 0.0);
+    assert!((power_heuristic(0.3, 1.7) + power_heuristic(1.7, 0.3) - 1.0).abs() < 1e-12);
+}
@@ -0,0 +1,138 @@
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::aabb::Aabb;
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::bvh::BvhNode;
+#[allow(unused_imports)]
+use crate::hittables::disk::Disk;
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::hittable_list::HittableList;
+#[allow(unused_imports)]
+use crate::hittables::plane::Plane;
+#[allow(unused_imports)]
+use crate::hittables::quad::{self, Quad};
+#[allow(unused_imports)]
+use crate::hittables::sphere::Sphere;
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+
+#[allow(dead_code)]
+fn material() -> Arc<dyn Material> {
+    Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)))
+}
+
+#[allow(dead_code)]
+fn hit(object: &dyn Hittable, origin: Point3, direction: Vec3) -> Option<HitRecord> {
+    let mut rec = HitRecord::default();
+    object
+        .hit(&Ray::new(origin, direction), &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(0))
+        .then_some(rec)
+}
+
+#[test]
+fn test_quad() {
+    let quad = Quad::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 4.0, 0.0), material());
+
+    let rec = hit(&quad, Point3::new(0.5, 3.0, 2.0), Vec3::new(0.0, 0.0, -1.0)).unwrap();
+    assert!((rec.t - 2.0).abs() < 1e-12);
+    assert!((rec.u - 0.25).abs() < 1e-12 && (rec.v - 0.75).abs() < 1e-12);
+    assert!(rec.front_face);
+    assert_eq!(rec.normal.e, [0.0, 0.0, 1.0]);
+
+    let back = hit(&quad, Point3::new(0.5, 3.0, -2.0), Vec3::new(0.0, 0.0, 1.0)).unwrap();
+    assert!(!back.front_face);
+    assert_eq!(back.normal.e, [0.0, 0.0, -1.0]);
+
+    assert!(hit(&quad, Point3::new(2.5, 1.0, 2.0), Vec3::new(0.0, 0.0, -1.0)).is_none());
+    assert!(hit(&quad, Point3::new(1.0, 1.0, 2.0), Vec3::new(1.0, 0.0, 0.0)).is_none());
+
+    // Flat along z, but padded so the slab test still finds it.
+    let bbox: Aabb = quad.bounding_box();
+    assert_eq!((bbox.x.min, bbox.x.max, bbox.y.max), (0.0, 2.0, 4.0));
+    assert!(bbox.z.size() > 0.0);
+}
+
+#[test]
+fn test_disk() {
+    let disk = Disk::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 2.0, 0.0), 1.0, material());
+
+    let rec = hit(&disk, Point3::new(1.0, 5.0, 3.0), Vec3::new(0.0, -1.0, 0.0)).unwrap();
+    assert!((rec.t - 3.0).abs() < 1e-12);
+    assert_eq!(rec.normal.e, [0.0, 1.0, 0.0]);
+    assert!((rec.u - 0.5).abs() < 1e-12 && (rec.v - 0.5).abs() < 1e-12);
+
+    let edge = hit(&disk, Point3::new(1.7, 5.0, 3.7), Vec3::new(0.0, -1.0, 0.0)).unwrap();
+    assert!((0.0..=1.0).contains(&edge.u) && (0.0..=1.0).contains(&edge.v));
+    assert!(hit(&disk, Point3::new(1.8, 5.0, 3.8), Vec3::new(0.0, -1.0, 0.0)).is_none());
+
+    let bbox: Aabb = disk.bounding_box();
+    assert_eq!((bbox.x.min, bbox.x.max, bbox.z.min, bbox.z.max), (0.0, 2.0, 2.0, 4.0));
+    assert!(bbox.y.size() < 0.001);
+}
+
+#[test]
+fn test_plane() {
+    let plane = Plane::new(Point3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0), material());
+
+    let rec = hit(&plane, Point3::new(1000.0, 1.0, -5000.0), Vec3::new(1.0, -1.0, 0.0)).unwrap();
+    assert!((rec.t - 2.0).abs() < 1e-9);
+    assert!((rec.p.y() + 1.0).abs() < 1e-9);
+    assert!(rec.front_face);
+    assert!(hit(&plane, Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)).is_none());
+
+    // Surface coordinates are distances along the plane.
+    let a = hit(&plane, Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).unwrap();
+    let b = hit(&plane, Point3::new(3.0, 1.0, 4.0), Vec3::new(0.0, -1.0, 0.0)).unwrap();
+    assert!(((b.u - a.u).hypot(b.v - a.v) - 5.0).abs() < 1e-9);
+
+    let bbox: Aabb = plane.bounding_box();
+    assert!(!bbox.is_bounded());
+    assert!(bbox.y.size() < 0.001 && bbox.x.size().is_infinite());
+}
+
+#[test]
+fn test_box() {
+    let cube = quad::make_box(Point3::new(1.0, 1.0, 1.0), Point3::new(-1.0, -1.0, -1.0), material());
+    assert_eq!(cube.objects().len(), 6);
+
+    // Every face is hit from outside on its front side.
+    let directions = [
+        Vec3::new(1.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0),
+    ];
+    for d in directions {
+        let rec = hit(&cube, -3.0 * d + Vec3::new(0.1, 0.2, 0.3), d).unwrap();
+        assert!((Vec3::dot(&rec.p, &d) + 1.0).abs() < 1e-9);
+        assert!(rec.front_face, "Face facing {:?} points inwards", (-d).e);
+    }
+}
+
+#[test]
+fn test_bvh_with_plane() {
+    let mut list = HittableList::new();
+    list.add(Arc::new(Plane::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), material())));
+    for i in 0..5 {
+        list.add(Arc::new(Sphere::new(Point3::new(i as f64 * 3.0, 1.0, 0.0), 1.0, material())));
+    }
+    let bvh = BvhNode::new(list);
+
+    let ground = hit(&bvh, Point3::new(1.5, 5.0, 50.0), Vec3::new(0.0, -1.0, 0.0)).unwrap();
+    assert!((ground.t - 5.0).abs() < 1e-9);
+    let sphere = hit(&bvh, Point3::new(6.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).unwrap();
+    assert!((sphere.t - 3.0).abs() < 1e-9);
+}
@@ -0,0 +1,146 @@
+#[allow(unused_imports)]
+use std::path::Path;
+
+#[allow(unused_imports)]
+use crate::output::transform::{ToneMap, TransferFunction};
+#[allow(unused_imports)]
+use crate::scene::error::SceneError;
+#[allow(unused_imports)]
+use crate::scene::loader::{self, Scene};
+
+#[allow(dead_code)]
+fn parse_error(source: &str) -> (usize, String) {
+    match loader::parse(source, None) {
+        Err(SceneError::Parse { line, message }) => (line, message),
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("scene parsed without error"),
+    }
+}
+
+#[test]
+fn test_parse_scene() {
+    let source = "
+        # A comment on its own line.
+        render width=64 height=32 samples=4 depth=8 seed=3 threads=1
+        output exposure=1 tonemap=aces transfer=gamma gamma=2.2
+        camera from=0,0,1 at=0,0,0 vfov=40   # trailing comment
+
+        material red lambertian albedo=0.8,0.1,0.1
+        material mirror metal albedo=0.9,0.9,0.9
+        material lamp light emit=4,4,4
+        background color=0,0,0
+        sphere center=0,0,-1 radius=0.5 material=red
+        sphere center=1,0,-1 radius=0.5 material=mirror
+        triangle a=-1,0,-2 b=1,0,-2 c=0,1,-2 material=red
+        quad q=-1,0,-3 u=2,0,0 v=0,1,0 material=red
+        disk center=0,0,-4 normal=0,0,1 radius=1 material=red
+        plane point=0,-0.5,0 normal=0,1,0 material=red
+        box min=0,0,0 max=1,1,1 material=mirror
+    ";
+    let scene: Scene = loader::parse(source, None).unwrap();
+
+    assert_eq!(scene.world.objects().len(), 7);
+    assert_eq!(scene.output.exposure, 1.0);
+    assert_eq!(scene.output.tone_map, ToneMap::AcesFilmic);
+    assert_eq!(scene.output.transfer, TransferFunction::Gamma(2.2));
+
+    let camera = scene.camera.build().unwrap();
+    assert_eq!(camera.image_width(), 64);
+    assert_eq!(camera.aspect_ratio(), 2.0);
+    assert_eq!(camera.samples_per_pixel(), 4);
+}
+
+#[test]
+fn test_scene_lights() {
+    // Bounded surfaces made of a light material are sampled as lights, wherever they are
+    // placed; planes, other materials and volumes are not.
+    let source = "
+        material lamp light emit=4,4,4
+        material white lambertian albedo=0.8,0.8,0.8
+        sphere center=0,3,0 radius=0.5 material=lamp
+        quad q=-1,4,-1 u=2,0,0 v=0,0,2 material=lamp rotate=0,45,0
+        box min=0,0,0 max=1,1,1 material=lamp translate_end=0,1,0
+        triangle a=-1,0,-2 b=1,0,-2 c=0,1,-2 material=lamp
+        disk center=0,5,0 normal=0,-1,0 radius=1 material=lamp
+        plane point=0,10,0 normal=0,-1,0 material=lamp
+        sphere center=0,0,0 radius=1 material=white
+        sphere center=0,0,0 radius=1 material=lamp density=0.5
+    ";
+    let scene: Scene = loader::parse(source, None).unwrap();
+    assert_eq!(scene.world.objects().len(), 8);
+    assert_eq!(scene.lights.objects().len(), 5);
+}
+
+#[test]
+fn test_default_scene_parses() {
+    assert!(loader::parse(crate::DEFAULT_SCENE, None).is_ok());
+}
+
+#[test]
+fn test_example_scenes_parse() {
+    for entry in std::fs::read_dir("scenes").unwrap() {
+        let path = entry.unwrap().path();
+        if let Err(err) = loader::load_file(&path, None) {
+            panic!("{}: {}", path.display(), err);
+        }
+    }
+}
+
+#[test]
+fn test_errors_report_line_numbers() {
+    assert_eq!(parse_error("\n\nteapot size=3"), (3, "unknown directive 'teapot'".to_string()));
+    assert_eq!(
+        parse_error("material a lambertian albedo=1,1,1\nsphere center=0,0,0 radius=1 material=b"),
+        (2, "undefined material 'b'".to_string()),
+    );
+    assert_eq!(parse_error("camera vfov=wide").0, 1);
+    assert_eq!(parse_error("camera vfov=40 zoom=2").1, "unknown property 'zoom' for 'camera'");
+    assert_eq!(parse_error("camera vfov=40 vfov=50").1, "duplicate property 'vfov'");
+    assert_eq!(parse_error("\ncamera from=1,2,3 at=1,2,3"), (2, "'from' and 'at' must be different points".to_string()));
+    assert_eq!(parse_error("camera from=0,5,0 at=0,0,0").1, "'up' must not be parallel to the view direction");
+    assert_eq!(parse_error("sphere center=0,0 radius=1 material=a").1, "invalid vector '0,0' for 'center', expected x,y,z");
+    assert_eq!(parse_error("material a lambertian").1, "missing property 'albedo'");
+    assert_eq!(
+        parse_error("material a lambertian albedo=1,1,1\nquad q=0,0,0 u=1,0,0 v=2,0,0 material=a"),
+        (2, "'u' and 'v' must span a nonzero area".to_string()),
+    );
+    assert_eq!(
+        parse_error("material a lambertian albedo=1,1,1\nbox min=0,0,0 max=1,0,1 material=a").1,
+        "'min' and 'max' must differ along every axis",
+    );
+    assert_eq!(
+        parse_error("material a light emit=1,1,1\nsphere center=0,0,0 radius=0 material=a"),
+        (2, "'radius' must be positive".to_string()),
+    );
+    assert_eq!(
+        parse_error("material a light emit=1,1,1\nsphere center=0,0,0 radius=-1 material=a").1,
+        "'radius' must be positive",
+    );
+    assert_eq!(parse_error("material a lambertian albedo=1,1,1\nmaterial a metal albedo=1,1,1").0, 2);
+    assert_eq!(parse_error("render samples=0").0, 1);
+    assert_eq!(parse_error("render aspect=16:0").1, "invalid aspect ratio '16:0'");
+    assert_eq!(parse_error("material a lambertian albedo=1,1,1 extra").0, 1);
+}
+
+#[test]
+fn test_values_must_be_finite_and_in_range() {
+    // NaN and infinities parse as floats, but are never valid values.
+    let sphere = |props: &str| parse_error(&format!("material a lambertian albedo=1,1,1\nsphere {} material=a", props));
+    assert_eq!(sphere("center=0,0,0 radius=nan"), (2, "invalid value 'nan' for 'radius'".to_string()));
+    assert_eq!(sphere("center=0,inf,0 radius=1").1, "invalid vector '0,inf,0' for 'center', expected x,y,z");
+    assert_eq!(sphere("center=0,0,0 radius=1 scale=NaN").1, "invalid vector 'NaN' for 'scale', expected x,y,z");
+    assert_eq!(
+        sphere("center=0,0,0 radius=1 matrix=1,0,0,0,0,1,0,0,0,0,1,-inf").1,
+        "invalid matrix '1,0,0,0,0,1,0,0,0,0,1,-inf', expected 12 or 16 values",
+    );
+    assert_eq!(parse_error("camera vfov=nan").1, "invalid value 'nan' for 'vfov'");
+    assert_eq!(parse_error("camera shutter=0,inf").1, "invalid shutter '0,inf', expected open,close");
+
+    assert_eq!(parse_error("camera vfov=0").1, "'vfov' must be between 0 and 180 degrees");
+    assert_eq!(parse_error("camera vfov=180").1, "'vfov' must be between 0 and 180 degrees");
+    assert_eq!(parse_error("output gamma=0").1, "'gamma' must be positive");
+    assert_eq!(parse_error("material a dielectric ior=-1.5").1, "'ior' must be positive");
+    assert_eq!(parse_error("material a metal albedo=1,1,1 fuzz=-0.1").1, "'fuzz' must be between 0 and 1");
+    assert_eq!(parse_error("material a metal albedo=1,1,1 fuzz=1.5").1, "'fuzz' must be between 0 and 1");
+    assert!(loader::parse("material a metal albedo=1,1,1 fuzz=1\ncamera vfov=179", None).is_ok());
+}
@@ -0,0 +1,260 @@
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use image::{DynamicImage, Rgb32FImage, RgbImage};
+
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::sphere::Sphere;
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::scene::loader;
+#[allow(unused_imports)]
+use crate::textures::checker::{CheckerTexture, UvCheckerTexture};
+#[allow(unused_imports)]
+use crate::textures::image_texture::ImageTexture;
+#[allow(unused_imports)]
+use crate::textures::noise::{MarbleTexture, NoiseTexture, WoodTexture};
+#[allow(unused_imports)]
+use crate::textures::perlin::Perlin;
+#[allow(unused_imports)]
+use crate::textures::solid_color::SolidColor;
+#[allow(unused_imports)]
+use crate::textures::texture::Texture;
+
+#[allow(dead_code)]
+const BLACK: Color = Color { e: [0.0, 0.0, 0.0] };
+#[allow(dead_code)]
+const WHITE: Color = Color { e: [1.0, 1.0, 1.0] };
+
+#[allow(dead_code)]
+fn origin() -> Point3 {
+    Point3::new(0.0, 0.0, 0.0)
+}
+
+#[test]
+fn test_solid_color() {
+    let tex = SolidColor::from_rgb(0.1, 0.2, 0.3);
+    assert_eq!(tex.value(0.7, 0.2, &Point3::new(5.0, 6.0, 7.0)).e, [0.1, 0.2, 0.3]);
+}
+
+#[test]
+fn test_checker() {
+    let tex = CheckerTexture::from_colors(2.0, BLACK, WHITE);
+    assert_eq!(tex.value(0.0, 0.0, &Point3::new(0.5, 0.5, 0.5)).e, BLACK.e);
+    assert_eq!(tex.value(0.0, 0.0, &Point3::new(2.5, 0.5, 0.5)).e, WHITE.e);
+    assert_eq!(tex.value(0.0, 0.0, &Point3::new(2.5, 2.5, 0.5)).e, BLACK.e);
+    // Cells keep alternating across the origin.
+    assert_eq!(tex.value(0.0, 0.0, &Point3::new(-0.5, 0.5, 0.5)).e, WHITE.e);
+    assert_eq!(tex.value(0.0, 0.0, &Point3::new(-0.5, -0.5, 0.5)).e, BLACK.e);
+    // Cells far beyond the range of the cell index stay well defined.
+    let far = Point3::new(1e300, f64::INFINITY, -f64::INFINITY);
+    assert_eq!(tex.value(0.0, 0.0, &far).e, BLACK.e);
+}
+
+#[test]
+fn test_uv_checker() {
+    let tex = UvCheckerTexture::from_colors(4.0, 2.0, BLACK, WHITE);
+    assert_eq!(tex.value(0.1, 0.1, &origin()).e, BLACK.e);
+    assert_eq!(tex.value(0.3, 0.1, &origin()).e, WHITE.e);
+    assert_eq!(tex.value(0.3, 0.6, &origin()).e, BLACK.e);
+    assert_eq!(tex.value(0.1, 0.6, &origin()).e, WHITE.e);
+}
+
+#[test]
+fn test_image_texture() {
+    // Top row red, green; bottom row blue, white.
+    let red = Color::new(1.0, 0.0, 0.0);
+    let green = Color::new(0.0, 1.0, 0.0);
+    let blue = Color::new(0.0, 0.0, 1.0);
+    let tex = ImageTexture::new(2, 2, vec![red, green, blue, WHITE]);
+
+    assert_eq!(tex.value(0.25, 0.75, &origin()).e, red.e);
+    assert_eq!(tex.value(0.75, 0.75, &origin()).e, green.e);
+    assert_eq!(tex.value(0.25, 0.25, &origin()).e, blue.e);
+    assert_eq!(tex.value(1.0, 0.0, &origin()).e, WHITE.e);
+    assert_eq!(tex.value(-3.0, 7.0, &origin()).e, red.e);
+}
+
+#[test]
+fn test_image_decoding() {
+    // 8-bit images are sRGB encoded; floating point images are linear.
+    let srgb = DynamicImage::ImageRgb8(RgbImage::from_pixel(1, 1, image::Rgb([188, 0, 255])));
+    let tex = ImageTexture::from_image(srgb);
+    assert!((tex.texel(0, 0).x() - 0.5).abs() < 0.005);
+    assert_eq!(tex.texel(0, 0).y(), 0.0);
+    assert!((tex.texel(0, 0).z() - 1.0).abs() < 1e-6);
+
+    let linear = DynamicImage::ImageRgb32F(Rgb32FImage::from_pixel(1, 1, image::Rgb([0.5, 4.0, 0.0])));
+    let tex = ImageTexture::from_image(linear);
+    assert_eq!(tex.texel(0, 0).e, [0.5, 4.0, 0.0]);
+}
+
+#[test]
+fn test_sphere_uv() {
+    let sphere = Sphere::new(Point3::new(0.0, 0.0, -5.0), 2.0, Arc::new(Lambertian::new(WHITE)));
+    let cases = [
+        (Vec3::new(1.0, 0.0, 0.0), 0.5, 0.5),
+        (Vec3::new(-1.0, 0.0, 0.0), 0.0, 0.5),
+        (Vec3::new(0.0, 1.0, 0.0), 0.5, 1.0),
+        (Vec3::new(0.0, -1.0, 0.0), 0.5, 0.0),
+        (Vec3::new(0.0, 0.0, 1.0), 0.25, 0.5),
+        (Vec3::new(0.0, 0.0, -1.0), 0.75, 0.5),
+    ];
+
+    for (direction, u, v) in cases {
+        // Shoot at the sphere from outside, along the direction towards its center.
+        let r = Ray::new(sphere.center + 10.0 * direction, -direction);
+        let mut rec = HitRecord::default();
+        assert!(sphere.hit(&r, &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(0)));
+        assert!((rec.u - u).abs() < 1e-9 && (rec.v - v).abs() < 1e-9, "{:?}: ({}, {})", direction.e, rec.u, rec.v);
+    }
+}
+
+#[test]
+fn test_lambertian_texture() {
+    let tex: Arc<dyn Texture> = Arc::new(UvCheckerTexture::from_colors(2.0, 2.0, BLACK, WHITE));
+    let material = Lambertian::from_texture(tex);
+
+    let r = Ray::new(Point3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    let mut rec = HitRecord::default();
+    rec.set_face_normal(&r, &Vec3::new(0.0, 0.0, 1.0));
+    let mut attenuation = Color::default();
+    let mut scattered = Ray::default();
+    let mut rng = Sampler::new(0);
+
+    (rec.u, rec.v) = (0.75, 0.25);
+    material.scatter(&r, &rec, &mut attenuation, &mut scattered, &mut rng);
+    assert_eq!(attenuation.e, WHITE.e);
+
+    (rec.u, rec.v) = (0.75, 0.75);
+    material.scatter(&r, &rec, &mut attenuation, &mut scattered, &mut rng);
+    assert_eq!(attenuation.e, BLACK.e);
+}
+
+#[test]
+fn test_scene_textures() {
+    let source = "
+        texture stripes uvchecker columns=8 rows=1 even=1,1,1 odd=0,0,0
+        texture checks checker scale=0.5 even=stripes odd=0.2,0.3,0.1
+        material ground lambertian texture=checks
+        material plain lambertian texture=0.5,0.5,0.5
+        sphere center=0,0,0 radius=1 material=ground
+    ";
+    assert!(loader::parse(source, None).is_ok());
+
+    let error = |source: &str| loader::parse(source, None).err().unwrap().to_string();
+    assert_eq!(error("texture a checker scale=1 even=b odd=0,0,0"), "line 1: 'even' is neither a texture nor a color");
+    assert_eq!(error("texture a granite"), "line 1: unknown texture type 'granite'");
+    assert_eq!(error("texture a solid color=1,1,1\ntexture a solid color=1,1,1"), "line 2: texture 'a' is already defined");
+    assert!(error("texture a image file=missing.png").starts_with("line 1: cannot load"));
+    assert_eq!(error("texture a checker scale=0 even=1,1,1 odd=0,0,0"), "line 1: 'scale' must be positive");
+    assert_eq!(error("texture a checker scale=nan even=1,1,1 odd=0,0,0"), "line 1: invalid value 'nan' for 'scale'");
+    assert_eq!(
+        error("texture a uvchecker columns=0 even=1,1,1 odd=0,0,0"),
+        "line 1: 'columns' and 'rows' must be positive",
+    );
+    assert_eq!(
+        error("texture a uvchecker columns=4 rows=-1 even=1,1,1 odd=0,0,0"),
+        "line 1: 'columns' and 'rows' must be positive",
+    );
+}
+
+#[test]
+fn test_perlin_noise() {
+    let noise = Perlin::new(&mut Sampler::new(1));
+    let same = Perlin::new(&mut Sampler::new(1));
+    let other = Perlin::new(&mut Sampler::new(2));
+
+    let mut rng = Sampler::new(0);
+    let mut differs: bool = false;
+    for _ in 0..1000 {
+        let p = Vec3::random_range(&mut rng, -50.0, 50.0);
+        let n: f64 = noise.noise(&p);
+        assert!((-1.0..=1.0).contains(&n));
+        assert_eq!(n, same.noise(&p), "Noise is not a function of the seed");
+        differs |= n != other.noise(&p);
+
+        // Continuous, and zero on the lattice.
+        assert!((n - noise.noise(&(p + Vec3::new(1e-7, 1e-7, 1e-7)))).abs() < 1e-5);
+        assert!(noise.noise(&Point3::new(p.x().floor(), p.y().floor(), p.z().floor())).abs() < 1e-12);
+
+        assert!(noise.turb(&p, 7) >= 0.0);
+    }
+    assert!(differs, "Different seeds gave the same noise");
+}
+
+#[test]
+fn test_noise_textures() {
+    let mut rng = Sampler::new(3);
+    let noise = NoiseTexture::new(4.0, &mut rng);
+    let marble = MarbleTexture::new(4.0, 10.0, Color::new(1.0, 0.5, 0.25), &mut rng);
+    let wood = WoodTexture::new(3.0, 1.0, Color::new(0.8, 0.6, 0.4), Color::new(0.4, 0.2, 0.1), &mut rng);
+
+    for _ in 0..1000 {
+        let p = Vec3::random_range(&mut rng, -5.0, 5.0);
+
+        let n: Color = noise.value(0.0, 0.0, &p);
+        assert!((0.0..=1.0).contains(&n.x()) && n.x() == n.y() && n.y() == n.z());
+
+        let m: Color = marble.value(0.0, 0.0, &p);
+        assert!((0.0..=1.0).contains(&m.x()) && (m.y() - 0.5 * m.x()).abs() < 1e-12);
+
+        let w: Color = wood.value(0.0, 0.0, &p);
+        assert!((0.4..=0.8).contains(&w.x()) && (0.1..=0.4).contains(&w.z()));
+    }
+
+    let source = "
+        texture a noise scale=4
+        texture b marble scale=2 turbulence=5 color=0.9,0.9,1 seed=7
+        texture c wood scale=8 light=0.8,0.6,0.4 dark=0.4,0.2,0.1
+        material m lambertian texture=b
+    ";
+    assert!(loader::parse(source, None).is_ok());
+}
+
+// Renders a sphere made of a noise texture, filling a tiny image.
+#[allow(dead_code)]
+fn render_noise(source: &str, seed: Option<u64>) -> Vec<f64> {
+    let scene = loader::parse(source, seed).unwrap();
+    let framebuffer = scene.camera.seed(1).build().unwrap().render(&scene.world);
+    (0..4).flat_map(|j| (0..4).map(move |i| (i, j))).map(|(i, j)| framebuffer.get(i, j).x()).collect()
+}
+
+#[test]
+fn test_noise_seeds() {
+    let scene = |extra: &str, seed: u64| {
+        format!(
+            "{}
+            render width=4 samples=1 seed={}
+            background color=1,1,1
+            texture n noise scale=8
+            material m lambertian texture=n
+            sphere center=0,0,-1.5 radius=1 material=m",
+            extra, seed,
+        )
+    };
+    // Noise does not depend on what else the scene declares before it, but follows the
+    // render seed.
+    let base: Vec<f64> = render_noise(&scene("", 3), None);
+    let extra = "texture m noise scale=8\nvolume noise min=5,5,5 max=6,6,6 density=1";
+    assert_eq!(base, render_noise(&scene(extra, 3), None));
+    assert_ne!(base, render_noise(&scene("", 4), None));
+
+    // A seed given to the loader replaces the render seed, as `--seed` does.
+    assert_eq!(base, render_noise(&scene("", 4), Some(3)));
+    assert_ne!(base, render_noise(&scene("", 3), Some(4)));
+}
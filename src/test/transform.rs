@@ -0,0 +1,51 @@
+#[allow(unused_imports)]
+use crate::geometry::vec3::Color;
+#[allow(unused_imports)]
+use crate::output::transform::{OutputTransform, ToneMap, TransferFunction};
+
+#[test]
+fn test_transfer_functions() {
+    assert_eq!(TransferFunction::Linear.encode(0.25), 0.25);
+    assert!((TransferFunction::Gamma(2.0).encode(0.25) - 0.5).abs() < 1e-12);
+
+    // Both branches of the sRGB curve, and continuity at the knee.
+    let srgb = TransferFunction::Srgb;
+    assert!((srgb.encode(0.001) - 0.01292).abs() < 1e-12);
+    assert!((srgb.encode(0.5) - 0.735_356_983_052_449_4).abs() < 1e-9);
+    assert!((srgb.encode(1.0) - 1.0).abs() < 1e-12);
+    assert!((srgb.encode(0.003_130_8) - srgb.encode(0.003_130_8 + 1e-12)).abs() < 1e-6);
+
+    // Decoding inverts encoding.
+    for transfer in [TransferFunction::Linear, TransferFunction::Gamma(2.2), srgb] {
+        for x in [0.0, 0.001, 0.2, 0.5, 1.0] {
+            assert!((transfer.decode(transfer.encode(x)) - x).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_tone_maps() {
+    assert_eq!(ToneMap::None.apply(4.0), 4.0);
+    assert_eq!(ToneMap::Reinhard.apply(1.0), 0.5);
+    assert_eq!(ToneMap::Reinhard.apply(-1.0), 0.0);
+
+    // ACES is monotonic and saturates near 1.
+    let aces = ToneMap::AcesFilmic;
+    assert_eq!(aces.apply(0.0), 0.0);
+    assert!(aces.apply(0.5) < aces.apply(1.0));
+    assert!(aces.apply(1.0) < aces.apply(10.0));
+    assert!((aces.apply(1e6) - 2.51 / 2.43).abs() < 1e-3);
+}
+
+#[test]
+fn test_output_transform() {
+    let linear = OutputTransform::new(0.0, ToneMap::None, TransferFunction::Linear);
+    assert_eq!(linear.apply(Color::new(0.25, 2.0, -1.0)).e, [0.25, 1.0, 0.0]);
+
+    // One stop of exposure doubles the radiance before encoding.
+    let brighter = OutputTransform::new(1.0, ToneMap::None, TransferFunction::Linear);
+    assert_eq!(brighter.apply(Color::new(0.25, 0.1, 0.0)).e, [0.5, 0.2, 0.0]);
+
+    let reinhard_gamma = OutputTransform::new(0.0, ToneMap::Reinhard, TransferFunction::Gamma(2.0));
+    assert!((reinhard_gamma.apply(Color::new(1.0, 1.0, 1.0)).x() - 0.5_f64.sqrt()).abs() < 1e-12);
+}
@@ -0,0 +1,129 @@
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::mesh::{Mesh, MeshData, MeshError};
+#[allow(unused_imports)]
+use crate::hittables::triangle::Triangle;
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::materials::material::Material;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+
+#[allow(dead_code)]
+fn material() -> Arc<dyn Material> {
+    Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)))
+}
+
+#[allow(dead_code)]
+fn unit_triangle() -> Triangle {
+    Triangle::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        material(),
+    )
+}
+
+#[allow(dead_code)]
+fn down(x: f64, y: f64) -> Ray {
+    Ray::new(Point3::new(x, y, 1.0), Vec3::new(0.0, 0.0, -1.0))
+}
+
+#[test]
+fn test_triangle_hit() {
+    let tri = unit_triangle();
+    let t = Interval::new(0.001, f64::INFINITY);
+    let mut rec = HitRecord::default();
+
+    assert!(tri.hit(&down(0.25, 0.5), &t, &mut rec, &mut Sampler::new(0)));
+    assert!((rec.t - 1.0).abs() < 1e-12);
+    assert!(rec.front_face);
+    assert_eq!(rec.normal.e, [0.0, 0.0, 1.0]);
+
+    // Without texture coordinates, (u, v) are the barycentrics of vertices 1 and 2.
+    assert!((rec.u - 0.25).abs() < 1e-12 && (rec.v - 0.5).abs() < 1e-12);
+
+    assert!(!tri.hit(&down(0.6, 0.6), &t, &mut rec, &mut Sampler::new(0)), "Hit outside the hypotenuse");
+    assert!(!tri.hit(&down(-0.1, 0.5), &t, &mut rec, &mut Sampler::new(0)), "Hit outside an edge");
+    assert!(!tri.hit(&Ray::new(Point3::new(0.2, 0.2, 1.0), Vec3::new(1.0, 0.0, 0.0)), &t, &mut rec, &mut Sampler::new(0)), "Parallel ray hit");
+    assert!(!tri.hit(&down(0.25, 0.25), &Interval::new(0.001, 0.5), &mut rec, &mut Sampler::new(0)), "Hit beyond ray_t");
+}
+
+#[test]
+fn test_triangle_back_face() {
+    let tri = unit_triangle();
+    let mut rec = HitRecord::default();
+    let up = Ray::new(Point3::new(0.2, 0.2, -1.0), Vec3::new(0.0, 0.0, 1.0));
+
+    assert!(tri.hit(&up, &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(0)));
+    assert!(!rec.front_face);
+    assert_eq!(rec.normal.e, [0.0, 0.0, -1.0]);
+}
+
+#[test]
+fn test_triangle_interpolates_attributes() {
+    let n = Vec3::unit_vector(Vec3::new(1.0, 0.0, 1.0));
+    let tri = unit_triangle()
+        .with_normals([Vec3::new(0.0, 0.0, 1.0), n, Vec3::new(0.0, 0.0, 1.0)])
+        .with_uvs([[0.0, 0.0], [2.0, 0.0], [0.0, 4.0]]);
+    let mut rec = HitRecord::default();
+
+    assert!(tri.hit(&down(0.5, 0.25), &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(0)));
+    assert!((rec.u - 1.0).abs() < 1e-12 && (rec.v - 1.0).abs() < 1e-12);
+    assert!((rec.normal.length() - 1.0).abs() < 1e-12);
+    assert!(rec.normal.x() > 0.0, "Shading normal was not interpolated");
+}
+
+#[test]
+fn test_mesh_matches_triangles() {
+    // A unit square in the z = 0 plane, made of two triangles sharing an edge.
+    let data = Arc::new(MeshData::new(
+        vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ],
+        vec![[0, 1, 2], [0, 2, 3]],
+    ));
+    let mesh = Mesh::new(Arc::clone(&data), material()).unwrap();
+    let other = Mesh::new(Arc::clone(&data), material()).unwrap();
+    assert!(Arc::ptr_eq(mesh.data(), other.data()), "Meshes copied the shared vertex data");
+
+    let t = Interval::new(0.001, f64::INFINITY);
+    let mut rec = HitRecord::default();
+    assert!(mesh.hit(&down(0.9, 0.1), &t, &mut rec, &mut Sampler::new(0)));
+    assert!(mesh.hit(&down(0.1, 0.9), &t, &mut rec, &mut Sampler::new(0)));
+    assert!((rec.t - 1.0).abs() < 1e-12);
+    assert!(!mesh.hit(&down(1.1, 0.5), &t, &mut rec, &mut Sampler::new(0)));
+
+    let bbox = mesh.bounding_box();
+    assert!(bbox.x.min <= 0.0 && bbox.x.max >= 1.0 && bbox.z.size() > 0.0);
+}
+
+#[test]
+fn test_mesh_rejects_bad_data() {
+    let data = MeshData::new(vec![Point3::new(0.0, 0.0, 0.0)], vec![[0, 1, 2]]);
+    let error = Mesh::new(Arc::new(data), material()).err();
+    assert_eq!(error, Some(MeshError::IndexOutOfRange { index: 1, vertex_count: 1 }));
+
+    let positions = vec![Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)];
+    let data = MeshData::new(positions.clone(), vec![[0, 1, 2]]).with_normals(vec![Vec3::new(0.0, 0.0, 1.0)]);
+    let error = Mesh::new(Arc::new(data), material()).err();
+    assert_eq!(error, Some(MeshError::NormalCount { count: 1, vertex_count: 3 }));
+
+    let data = MeshData::new(positions, vec![[0, 1, 2]]).with_uvs(vec![[0.0, 0.0]; 4]);
+    let error = Mesh::new(Arc::new(data), material()).err();
+    assert_eq!(error, Some(MeshError::UvCount { count: 4, vertex_count: 3 }));
+}
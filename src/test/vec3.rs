@@ -247,4 +247,40 @@ fn test_mul_fraction() {
     let scaled_v2 = v * 0.5;
     assert_eq!(scaled_v1.e, [0.5, 1.0, 1.5]);
     assert_eq!(scaled_v2.e, [0.5, 1.0, 1.5]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_neg() {
+    let v = Vec3::new(1.0, -2.0, 3.0);
+    assert_eq!((-v).e, [-1.0, 2.0, -3.0]);
+}
+
+#[test]
+fn test_near_zero() {
+    assert!(Vec3::new(1e-9, -1e-9, 0.0).near_zero());
+    assert!(!Vec3::new(1e-9, 1e-3, 0.0).near_zero());
+}
+
+#[test]
+fn test_reflect() {
+    let v = Vec3::new(1.0, -1.0, 0.0);
+    let n = Vec3::new(0.0, 1.0, 0.0);
+    assert_eq!(Vec3::reflect(&v, &n).e, [1.0, 1.0, 0.0]);
+}
+
+#[test]
+fn test_refract() {
+    let n = Vec3::new(0.0, 1.0, 0.0);
+
+    // Normal incidence passes straight through regardless of the index ratio.
+    let straight = Vec3::refract(&Vec3::new(0.0, -1.0, 0.0), &n, 1.0 / 1.5);
+    assert!((straight - Vec3::new(0.0, -1.0, 0.0)).near_zero());
+
+    // Snell's law: sin(theta_t) = eta * sin(theta_i).
+    let uv = Vec3::unit_vector(Vec3::new(1.0, -1.0, 0.0));
+    let eta = 1.0 / 1.5;
+    let refracted = Vec3::refract(&uv, &n, eta);
+    assert!((refracted.length() - 1.0).abs() < 1e-10, "Refracted vector is not unit length");
+    assert!((refracted.x() - eta * uv.x()).abs() < 1e-10, "Tangential component violates Snell's law");
+    assert!(refracted.y() < 0.0, "Refracted vector did not cross the surface");
+}
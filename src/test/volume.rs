@@ -0,0 +1,243 @@
+#[allow(unused_imports)]
+use std::fs;
+#[allow(unused_imports)]
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use crate::geometry::interval::Interval;
+#[allow(unused_imports)]
+use crate::geometry::ray::Ray;
+#[allow(unused_imports)]
+use crate::geometry::vec3::{Color, Point3, Vec3};
+#[allow(unused_imports)]
+use crate::hittables::bvh::BvhNode;
+#[allow(unused_imports)]
+use crate::hittables::constant_medium::ConstantMedium;
+#[allow(unused_imports)]
+use crate::hittables::heterogeneous_medium::HeterogeneousMedium;
+#[allow(unused_imports)]
+use crate::hittables::hittable::{HitRecord, Hittable};
+#[allow(unused_imports)]
+use crate::hittables::hittable_list::HittableList;
+#[allow(unused_imports)]
+use crate::hittables::quad;
+#[allow(unused_imports)]
+use crate::hittables::transform::Transform;
+#[allow(unused_imports)]
+use crate::materials::lambertian::Lambertian;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::scene::loader;
+#[allow(unused_imports)]
+use crate::volumes::density::DensityField;
+#[allow(unused_imports)]
+use crate::volumes::noise_density::NoiseDensity;
+#[allow(unused_imports)]
+use crate::volumes::voxel_grid::{RawFormat, VoxelGrid};
+
+// A grid whose density falls from 1 at x = 1/4 to 0 at x = 3/4, and so is 1/2 all along
+// the plane x = 1/2, half its maximum.
+#[allow(dead_code)]
+fn ramp() -> Arc<dyn DensityField> {
+    Arc::new(VoxelGrid::new([2, 1, 1], vec![1.0, 0.0]))
+}
+
+// A ray along z through the middle of the unit cube, which it crosses from t = 4 to t = 5.
+#[allow(dead_code)]
+fn through_middle() -> Ray {
+    Ray::new(Point3::new(0.5, 0.5, 5.0), Vec3::new(0.0, 0.0, -1.0))
+}
+
+#[test]
+fn test_voxel_grid() {
+    let grid = VoxelGrid::new([2, 2, 1], vec![0.0, 1.0, 2.0, -3.0]);
+    assert_eq!(grid.resolution(), [2, 2, 1]);
+    assert_eq!(grid.value(0, 1, 0), 2.0);
+    assert_eq!(grid.value(1, 1, 0), 0.0);
+    assert_eq!(grid.max_density(), 2.0);
+
+    // Samples sit at voxel centers, and hold their value out to the faces.
+    assert_eq!(grid.density(&Point3::new(0.25, 0.25, 0.5)), 0.0);
+    assert_eq!(grid.density(&Point3::new(0.0, 1.0, 0.0)), 2.0);
+    assert!((grid.density(&Point3::new(0.5, 0.25, 0.5)) - 0.5).abs() < 1e-12);
+    assert!((grid.density(&Point3::new(0.5, 0.5, 0.5)) - 0.75).abs() < 1e-12);
+    assert_eq!(grid.density(&Point3::new(1.01, 0.5, 0.5)), 0.0);
+}
+
+#[test]
+fn test_raw_voxels() {
+    let bytes: Vec<u8> = [0.5_f32, 2.0].iter().flat_map(|value| value.to_le_bytes()).collect();
+    let grid = VoxelGrid::from_raw_bytes(&bytes, [1, 1, 2], RawFormat::F32).unwrap();
+    assert_eq!((grid.value(0, 0, 0), grid.value(0, 0, 1)), (0.5, 2.0));
+
+    let grid = VoxelGrid::from_raw_bytes(&[0, 51, 255], [3, 1, 1], RawFormat::U8).unwrap();
+    assert_eq!(grid.value(1, 0, 0), 0.2_f32 as f64);
+    assert_eq!(grid.max_density(), 1.0);
+
+    let err = VoxelGrid::from_raw_bytes(&[0, 1, 2], [2, 2, 1], RawFormat::U8).err().unwrap();
+    assert_eq!(err.to_string(), "expected 4 bytes for 2x2x1 samples, found 3");
+}
+
+#[test]
+fn test_noise_density() {
+    let cloud = NoiseDensity::new(4.0, &mut Sampler::new(1));
+    let same = NoiseDensity::new(4.0, &mut Sampler::new(1));
+    let mut rng = Sampler::new(2);
+    for _ in 0..1000 {
+        let p = Point3::new(rng.random_double(), rng.random_double(), rng.random_double());
+        let density: f64 = cloud.density(&p);
+        assert!((0.0..=cloud.max_density()).contains(&density));
+        assert_eq!(density, same.density(&p));
+        if (p - Point3::new(0.5, 0.5, 0.5)).length() >= 0.5 {
+            assert_eq!(density, 0.0);
+        }
+    }
+    assert_eq!(cloud.density(&Point3::new(0.5, 0.5, 0.5)), 1.0);
+}
+
+#[test]
+fn test_tracking() {
+    // Over the unit length crossed, the optical depth is 3 * 1/2.
+    let min = Point3::new(0.0, 0.0, 0.0);
+    let max = Point3::new(1.0, 1.0, 1.0);
+    let medium = HeterogeneousMedium::new(ramp(), min, max, 3.0, Color::new(0.5, 0.5, 0.5));
+    let expected: f64 = (-1.5_f64).exp();
+    let r = through_middle();
+    let t = Interval::new(0.001, f64::INFINITY);
+    let mut rng = Sampler::new(3);
+
+    let trials = 20000;
+    let mut passed = 0;
+    let mut ratio_sum: f64 = 0.0;
+    for _ in 0..trials {
+        let mut rec = HitRecord::default();
+        if medium.hit(&r, &t, &mut rec, &mut rng) {
+            assert!(rec.t > 4.0 - 1e-3 && rec.t < 5.0 + 1e-3);
+        } else {
+            passed += 1;
+        }
+        ratio_sum += medium.transmittance(&r, &t, &mut rng);
+    }
+    let delta: f64 = passed as f64 / trials as f64;
+    let ratio: f64 = ratio_sum / trials as f64;
+    assert!((delta - expected).abs() < 0.015, "{} != {}", delta, expected);
+    assert!((ratio - expected).abs() < 0.01, "{} != {}", ratio, expected);
+
+    // Ray segments ending before the volume, or missing it, pass untouched.
+    assert_eq!(medium.transmittance(&r, &Interval::new(0.001, 3.9), &mut rng), 1.0);
+    let miss = Ray::new(Point3::new(2.0, 0.5, 5.0), Vec3::new(0.0, 0.0, -1.0));
+    assert_eq!(medium.transmittance(&miss, &t, &mut rng), 1.0);
+}
+
+#[test]
+fn test_emission() {
+    // A thick, black body glows with its full emission wherever the emission field is one.
+    let glow: Arc<dyn DensityField> = Arc::new(VoxelGrid::new([1, 1, 1], vec![1.0]));
+    let fire = HeterogeneousMedium::new(ramp(), Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0), 1000.0, Color::default())
+        .with_emission(glow, Color::new(4.0, 2.0, 1.0));
+
+    let mut rec = HitRecord::default();
+    assert!(fire.hit(&through_middle(), &Interval::new(0.001, f64::INFINITY), &mut rec, &mut Sampler::new(4)));
+    let emitted: Color = rec.mat.unwrap().emitted(rec.u, rec.v, &rec.p);
+    assert_eq!(emitted.e, [4.0, 2.0, 1.0]);
+}
+
+#[test]
+fn test_transmittance_of_containers() {
+    let t = Interval::new(0.001, f64::INFINITY);
+    let mut rng = Sampler::new(5);
+    let material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    let boundary: Arc<dyn Hittable> = Arc::new(quad::make_box(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0), material.clone()));
+    let fog: Arc<dyn Hittable> = Arc::new(ConstantMedium::new(Arc::clone(&boundary), 2.0, Color::new(1.0, 1.0, 1.0)));
+    let moved_fog: Arc<dyn Hittable> = Arc::new(Transform::translate(Arc::clone(&fog), Vec3::new(0.0, 0.0, 2.0)));
+
+    // Two unit lengths of fog of density 2 along the ray, with exact transmittance.
+    let mut world = HittableList::new();
+    world.add(Arc::clone(&fog));
+    world.add(Arc::clone(&moved_fog));
+    let r = Ray::new(Point3::new(0.5, 0.5, 10.0), Vec3::new(0.0, 0.0, -1.0));
+    assert!((world.transmittance(&r, &t, &mut rng) - (-4.0_f64).exp()).abs() < 1e-9);
+    let bvh = BvhNode::new(world);
+    assert!((bvh.transmittance(&r, &t, &mut rng) - (-4.0_f64).exp()).abs() < 1e-9);
+
+    // A surface in the way blocks everything.
+    let mut world = HittableList::new();
+    world.add(fog);
+    world.add(Arc::new(Transform::translate(boundary, Vec3::new(0.0, 0.0, 4.0))));
+    assert_eq!(BvhNode::new(world).transmittance(&r, &t, &mut rng), 0.0);
+}
+
+#[test]
+fn test_scene_volumes() {
+    let dir = std::env::temp_dir().join(format!("ray_tracing_{}_volumes", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("cloud.raw"), [0_u8, 128, 255, 64]).unwrap();
+    fs::write(dir.join("test.scene"), "
+        volume grid file=cloud.raw resolution=2,2,1 format=u8 min=0,0,0 max=1,1,1 density=2
+        volume grid file=cloud.raw emit_file=cloud.raw resolution=4,1,1 format=u8 min=0,0,0 max=1,1,1 density=2 emit=4,1,0 rotate=0,45,0
+        volume noise min=-1,0,-1 max=1,2,1 density=4 albedo=0.9,0.9,0.9 scale=3 seed=7
+    ").unwrap();
+    fs::write(dir.join("bad.scene"), "volume grid file=cloud.raw resolution=3,1,1 format=u8 min=0,0,0 max=1,1,1 density=2").unwrap();
+
+    let scene = loader::load_file(&dir.join("test.scene"), None);
+    let bad = loader::load_file(&dir.join("bad.scene"), None);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(scene.unwrap().world.objects().len(), 3);
+    assert!(bad.err().unwrap().to_string().ends_with("expected 3 bytes for 3x1x1 samples, found 4"));
+
+    let parse = |source: &str| loader::parse(source, None).map(|_| ()).map_err(|err| err.to_string());
+    assert_eq!(
+        parse("volume noise min=0,0,0 max=1,0,1 density=1"),
+        Err(String::from("line 1: 'min' must be below 'max' on every axis")),
+    );
+    assert_eq!(
+        parse("volume noise min=nan,-1,-1 max=1,1,1 density=1"),
+        Err(String::from("line 1: invalid vector 'nan,-1,-1' for 'min', expected x,y,z")),
+    );
+    assert_eq!(
+        parse("volume noise min=-1,-1,-1 max=1,1,1 density=nan"),
+        Err(String::from("line 1: invalid value 'nan' for 'density'")),
+    );
+    assert_eq!(
+        parse("volume noise min=-1,-1,-1 max=1,1,1 density=1 scale=0"),
+        Err(String::from("line 1: 'scale' must be positive")),
+    );
+    assert_eq!(
+        parse("volume noise min=-1,-1,-1 max=1,1,1 density=1 scale=nan"),
+        Err(String::from("line 1: invalid value 'nan' for 'scale'")),
+    );
+    assert_eq!(
+        parse("volume smoke min=0,0,0 max=1,1,1 density=1"),
+        Err(String::from("line 1: unknown volume type 'smoke'")),
+    );
+}
+
+#[test]
+fn test_scene_emission() {
+    // An emitting volume without an albedo glows against a black background.
+    let render = |volume: &str| {
+        let source = format!(
+            "render width=4 samples=8 seed=1
+            background color=0,0,0
+            camera from=0,0,4 at=0,0,0 vfov=30
+            {}",
+            volume,
+        );
+        let scene = loader::parse(&source, None).map_err(|err| err.to_string())?;
+        let framebuffer = scene.camera.build().unwrap().render(&scene.world);
+        let red: f64 = (0..4).flat_map(|j| (0..4).map(move |i| (i, j))).map(|(i, j)| framebuffer.get(i, j).x()).sum();
+        Ok::<f64, String>(red / 16.0)
+    };
+
+    let fire = "volume noise min=-1,-1,-1 max=1,1,1 density=20 emit=50,10,0 seed=3";
+    let glow: f64 = render(fire).unwrap();
+    assert!(glow > 1.0, "{}", glow);
+    let grey: f64 = render(&format!("{} albedo=0.5,0.5,0.5", fire)).unwrap();
+    assert!(grey > 0.0 && grey < glow, "{} {}", grey, glow);
+    assert_eq!(
+        render("volume noise min=-1,-1,-1 max=1,1,1 density=20 emit=50,10,0 albedo=1,1,1"),
+        Err(String::from("line 4: 'emit' requires an 'albedo' below one, or none at all")),
+    );
+}
@@ -0,0 +1,153 @@
+#[allow(unused_imports)]
+use std::f64::consts::PI;
+
+#[allow(unused_imports)]
+use crate::geometry::onb::Onb;
+#[allow(unused_imports)]
+use crate::geometry::vec3::Vec3;
+#[allow(unused_imports)]
+use crate::sampling::sampler::Sampler;
+#[allow(unused_imports)]
+use crate::sampling::warp;
+
+#[allow(dead_code)]
+fn near(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() < tolerance
+}
+
+// Points of a regular n by n grid, each at the center of its cell.
+#[allow(dead_code)]
+fn grid(n: usize) -> impl Iterator<Item = [f64; 2]> {
+    (0..n * n).map(move |i| [((i % n) as f64 + 0.5) / n as f64, ((i / n) as f64 + 0.5) / n as f64])
+}
+
+#[test]
+fn test_onb() {
+    for n in [Vec3::new(0.0, 0.0, 2.0), Vec3::new(-3.0, 0.1, 0.0), Vec3::new(1.0, -2.0, 3.0)] {
+        let onb = Onb::new(&n);
+        assert!(near(Vec3::dot(&onb.w, &Vec3::unit_vector(n)), 1.0, 1e-12));
+        for (a, b) in [(onb.u, onb.v), (onb.v, onb.w), (onb.w, onb.u)] {
+            assert!(near(a.length(), 1.0, 1e-12));
+            assert!(near(Vec3::dot(&a, &b), 0.0, 1e-12));
+        }
+        // Right-handed.
+        assert!((Vec3::cross(&onb.u, &onb.v) - onb.w).near_zero());
+
+        let a = Vec3::new(0.3, -1.2, 2.5);
+        assert!((onb.to_local(&onb.transform(&a)) - a).near_zero());
+        assert!((onb.transform(&Vec3::new(0.0, 0.0, 1.0)) - onb.w).near_zero());
+    }
+}
+
+#[test]
+fn test_disk_warps() {
+    assert_eq!(warp::square_to_concentric_disk([0.5, 0.5]), Vec3::default());
+    assert!(near(warp::square_to_concentric_disk([1.0, 0.5]).x(), 1.0, 1e-12));
+
+    for disk in [warp::square_to_uniform_disk, warp::square_to_concentric_disk] {
+        // Evenly spread points land evenly over the disk: a quarter inside half the radius.
+        let n = 100;
+        let mut inner = 0;
+        for u in grid(n) {
+            let p: Vec3 = disk(u);
+            assert!(p.length_squared() <= 1.0 + 1e-12 && p.z() == 0.0);
+            if p.length() < 0.5 {
+                inner += 1;
+            }
+        }
+        assert!(near(inner as f64 / (n * n) as f64, 0.25, 0.01), "{}", inner);
+    }
+    assert!(near(warp::uniform_disk_pdf() * PI, 1.0, 1e-12));
+}
+
+// A direction warp, its density, and the integrals of z and z² over the directions it
+// covers.
+#[allow(dead_code)]
+struct DirectionWarp {
+    square_to: fn([f64; 2]) -> Vec3,
+    pdf: fn(&Vec3) -> f64,
+    integral_z: f64,
+    integral_z2: f64,
+}
+
+#[test]
+fn test_direction_warps() {
+    // With cos_theta_max = 0.8.
+    let cone = |u: [f64; 2]| warp::square_to_uniform_cone(u, 0.8);
+    let warps: [DirectionWarp; 4] = [
+        DirectionWarp {
+            square_to: warp::square_to_uniform_sphere,
+            pdf: |_| warp::uniform_sphere_pdf(),
+            integral_z: 0.0,
+            integral_z2: 4.0 * PI / 3.0,
+        },
+        DirectionWarp {
+            square_to: warp::square_to_uniform_hemisphere,
+            pdf: |_| warp::uniform_hemisphere_pdf(),
+            integral_z: PI,
+            integral_z2: 2.0 * PI / 3.0,
+        },
+        DirectionWarp {
+            square_to: warp::square_to_cosine_hemisphere,
+            pdf: |d| warp::cosine_hemisphere_pdf(d.z()),
+            integral_z: PI,
+            integral_z2: 2.0 * PI / 3.0,
+        },
+        DirectionWarp {
+            square_to: cone,
+            pdf: |_| warp::uniform_cone_pdf(0.8),
+            integral_z: PI * (1.0 - 0.64),
+            integral_z2: 2.0 * PI * (1.0 - 0.512) / 3.0,
+        },
+    ];
+
+    // Each warp gives unit directions, and dividing by its density gives unbiased estimates
+    // of integrals over them.
+    let n = 200;
+    let count = (n * n) as f64;
+    for w in warps {
+        let (mut z, mut z2) = (0.0, 0.0);
+        for u in grid(n) {
+            let d: Vec3 = (w.square_to)(u);
+            assert!(near(d.length(), 1.0, 1e-12));
+            let pdf: f64 = (w.pdf)(&d);
+            assert!(pdf > 0.0);
+            z += d.z() / pdf;
+            z2 += d.z() * d.z() / pdf;
+        }
+        assert!(near(z / count, w.integral_z, 1e-3), "{} != {}", z / count, w.integral_z);
+        assert!(near(z2 / count, w.integral_z2, 1e-3), "{} != {}", z2 / count, w.integral_z2);
+    }
+
+    // The cone stays within its angle.
+    for u in grid(20) {
+        assert!(cone(u).z() >= 0.8 - 1e-12);
+    }
+}
+
+#[test]
+fn test_triangle_warp() {
+    let n = 100;
+    let (mut sum1, mut sum2) = (0.0, 0.0);
+    for u in grid(n) {
+        let (b1, b2) = warp::square_to_uniform_triangle(u);
+        assert!(b1 >= 0.0 && b2 >= 0.0 && b1 + b2 <= 1.0);
+        sum1 += b1;
+        sum2 += b2;
+    }
+    // The centroid.
+    let count = (n * n) as f64;
+    assert!(near(sum1 / count, 1.0 / 3.0, 1e-3) && near(sum2 / count, 1.0 / 3.0, 1e-3));
+}
+
+#[test]
+fn test_hemisphere_samples() {
+    // Samples drawn around a normal stay on its side.
+    let mut rng = Sampler::new(1);
+    let normal = Vec3::new(-1.0, 2.0, 0.5);
+    for _ in 0..1000 {
+        let d: Vec3 = Vec3::random_on_hemisphere(&mut rng, &normal);
+        assert!(near(d.length(), 1.0, 1e-12) && Vec3::dot(&d, &normal) >= 0.0);
+        assert!(Vec3::random_in_unit_disk(&mut rng).length_squared() < 1.0);
+    }
+}
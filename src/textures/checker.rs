@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::geometry::vec3::{Color, Point3};
+
+use super::solid_color::SolidColor;
+use super::texture::Texture;
+
+// A solid checker pattern of cubes with sides of `scale` world units, alternating between
+// two textures. Being defined in space, it does not depend on surface coordinates. The
+// scale must be positive.
+pub struct CheckerTexture {
+    inv_scale: f64,
+    even: Arc<dyn Texture>,
+    odd: Arc<dyn Texture>,
+}
+
+impl CheckerTexture {
+    pub fn new(scale: f64, even: Arc<dyn Texture>, odd: Arc<dyn Texture>) -> Self {
+        assert!(scale > 0_f64, "checker scale must be positive, got {}", scale);
+        Self { inv_scale: 1_f64 / scale, even, odd }
+    }
+
+    pub fn from_colors(scale: f64, c1: Color, c2: Color) -> Self {
+        Self::new(scale, Arc::new(SolidColor::new(c1)), Arc::new(SolidColor::new(c2)))
+    }
+}
+
+impl Texture for CheckerTexture {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        let x: i64 = (self.inv_scale * p.x()).floor() as i64;
+        let y: i64 = (self.inv_scale * p.y()).floor() as i64;
+        let z: i64 = (self.inv_scale * p.z()).floor() as i64;
+
+        if is_even(&[x, y, z]) {
+            self.even.value(u, v, p)
+        } else {
+            self.odd.value(u, v, p)
+        }
+    }
+}
+
+// A checker pattern over surface coordinates, with `columns` squares across u and `rows`
+// squares across v for each unit of the coordinates. Both counts must be positive.
+pub struct UvCheckerTexture {
+    columns: f64,
+    rows: f64,
+    even: Arc<dyn Texture>,
+    odd: Arc<dyn Texture>,
+}
+
+impl UvCheckerTexture {
+    pub fn new(columns: f64, rows: f64, even: Arc<dyn Texture>, odd: Arc<dyn Texture>) -> Self {
+        assert!(columns > 0_f64 && rows > 0_f64, "checker columns and rows must be positive");
+        Self { columns, rows, even, odd }
+    }
+
+    pub fn from_colors(columns: f64, rows: f64, c1: Color, c2: Color) -> Self {
+        Self::new(columns, rows, Arc::new(SolidColor::new(c1)), Arc::new(SolidColor::new(c2)))
+    }
+}
+
+impl Texture for UvCheckerTexture {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        let i: i64 = (u * self.columns).floor() as i64;
+        let j: i64 = (v * self.rows).floor() as i64;
+
+        if is_even(&[i, j]) {
+            self.even.value(u, v, p)
+        } else {
+            self.odd.value(u, v, p)
+        }
+    }
+}
+
+// Whether the cell with the given integer coordinates has an even coordinate sum. Far from
+// the origin the coordinates saturate, so their parities are added rather than the
+// coordinates themselves.
+fn is_even(cell: &[i64]) -> bool {
+    cell.iter().map(|c| c.rem_euclid(2)).sum::<i64>() % 2 == 0
+}
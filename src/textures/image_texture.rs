@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use image::{DynamicImage, ImageResult};
+
+use crate::geometry::vec3::{Color, Point3};
+use crate::output::transform::TransferFunction;
+
+use super::texture::Texture;
+
+// A texture sampled from an image, with u running left to right and v bottom to top.
+// Coordinates outside [0,1] are clamped to the border. Texels are stored as linear color:
+// integer images are assumed to be sRGB encoded, and floating point images (such as EXR
+// and Radiance HDR) to be linear already.
+pub struct ImageTexture {
+    width: u32,
+    height: u32,
+    texels: Vec<Color>,
+}
+
+impl ImageTexture {
+    // Texels are given in row-major order, starting from the top left.
+    pub fn new(width: u32, height: u32, texels: Vec<Color>) -> Self {
+        assert_eq!(texels.len(), width as usize * height as usize, "texel count does not match image size");
+        Self { width, height, texels }
+    }
+
+    pub fn load(path: &Path) -> ImageResult<Self> {
+        Ok(Self::from_image(image::open(path)?))
+    }
+
+    pub fn from_image(img: DynamicImage) -> Self {
+        let encoded: bool = !matches!(img, DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_));
+        let rgb = img.into_rgb32f();
+
+        let decode = |x: f32| -> f64 {
+            if encoded { TransferFunction::Srgb.decode(x as f64) } else { x as f64 }
+        };
+        let texels: Vec<Color> = rgb
+            .pixels()
+            .map(|pixel| Color::new(decode(pixel[0]), decode(pixel[1]), decode(pixel[2])))
+            .collect();
+
+        Self::new(rgb.width(), rgb.height(), texels)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn texel(&self, i: u32, j: u32) -> Color {
+        self.texels[(j * self.width + i) as usize]
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f64, v: f64, _p: &Point3) -> Color {
+        if self.texels.is_empty() {
+            return Color::new(0.0, 1.0, 1.0);
+        }
+
+        // Flip v so that it runs from the bottom row of the image.
+        let u: f64 = u.clamp(0_f64, 1_f64);
+        let v: f64 = 1_f64 - v.clamp(0_f64, 1_f64);
+
+        let i: u32 = ((u * self.width as f64) as u32).min(self.width - 1);
+        let j: u32 = ((v * self.height as f64) as u32).min(self.height - 1);
+        self.texel(i, j)
+    }
+}
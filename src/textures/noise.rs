@@ -0,0 +1,74 @@
+use crate::geometry::vec3::{Color, Point3};
+
+use crate::sampling::sampler::Sampler;
+
+use super::perlin::Perlin;
+use super::texture::Texture;
+
+// Number of octaves summed by the turbulent textures.
+const TURBULENCE_DEPTH: u32 = 7;
+
+// Plain Perlin noise, mapped from [-1,1] to a gray in [0,1]. `scale` sets the frequency of
+// the noise in world space.
+pub struct NoiseTexture {
+    noise: Perlin,
+    scale: f64,
+}
+
+impl NoiseTexture {
+    pub fn new(scale: f64, rng: &mut Sampler) -> Self {
+        Self { noise: Perlin::new(rng), scale }
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        Color::new(1.0, 1.0, 1.0) * 0.5 * (1_f64 + self.noise.noise(&(self.scale * *p)))
+    }
+}
+
+// Marble-like veins: bands of `color` along z, with their phase disturbed by turbulence.
+pub struct MarbleTexture {
+    noise: Perlin,
+    scale: f64,
+    turbulence: f64,
+    color: Color,
+}
+
+impl MarbleTexture {
+    pub fn new(scale: f64, turbulence: f64, color: Color, rng: &mut Sampler) -> Self {
+        Self { noise: Perlin::new(rng), scale, turbulence, color }
+    }
+}
+
+impl Texture for MarbleTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let phase: f64 = self.scale * p.z() + self.turbulence * self.noise.turb(p, TURBULENCE_DEPTH);
+        self.color * 0.5 * (1_f64 + phase.sin())
+    }
+}
+
+// Wood-like growth rings around the y axis, `scale` rings per world unit, blending from
+// `light` to `dark` across each ring. Turbulence warps the rings.
+pub struct WoodTexture {
+    noise: Perlin,
+    scale: f64,
+    turbulence: f64,
+    light: Color,
+    dark: Color,
+}
+
+impl WoodTexture {
+    pub fn new(scale: f64, turbulence: f64, light: Color, dark: Color, rng: &mut Sampler) -> Self {
+        Self { noise: Perlin::new(rng), scale, turbulence, light, dark }
+    }
+}
+
+impl Texture for WoodTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let distance: f64 = p.x().hypot(p.z());
+        let rings: f64 = self.scale * distance + self.turbulence * self.noise.turb(p, TURBULENCE_DEPTH);
+        let t: f64 = rings - rings.floor();
+        (1_f64 - t) * self.light + t * self.dark
+    }
+}
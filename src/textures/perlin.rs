@@ -0,0 +1,108 @@
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::sampling::sampler::Sampler;
+
+const POINT_COUNT: usize = 256;
+
+// Perlin gradient noise: random unit gradients on the integer lattice, selected by hashing
+// the lattice coordinates through three random permutations, and blended with Hermite
+// smoothing. The lattice is generated from a `Sampler`, so the noise is a pure function of
+// the sampler's seed.
+pub struct Perlin {
+    randvec: [Vec3; POINT_COUNT],
+    perm_x: [usize; POINT_COUNT],
+    perm_y: [usize; POINT_COUNT],
+    perm_z: [usize; POINT_COUNT],
+}
+
+impl Perlin {
+    pub fn new(rng: &mut Sampler) -> Self {
+        let mut randvec: [Vec3; POINT_COUNT] = [Vec3::default(); POINT_COUNT];
+        for v in randvec.iter_mut() {
+            *v = Vec3::unit_vector(Vec3::random_range(rng, -1_f64, 1_f64));
+        }
+
+        Self {
+            randvec,
+            perm_x: Self::generate_perm(rng),
+            perm_y: Self::generate_perm(rng),
+            perm_z: Self::generate_perm(rng),
+        }
+    }
+
+    // Returns the noise at `p`, in [-1,1]. It is zero at every lattice point.
+    pub fn noise(&self, p: &Point3) -> f64 {
+        let u: f64 = p.x() - p.x().floor();
+        let v: f64 = p.y() - p.y().floor();
+        let w: f64 = p.z() - p.z().floor();
+
+        let i: i64 = p.x().floor() as i64;
+        let j: i64 = p.y().floor() as i64;
+        let k: i64 = p.z().floor() as i64;
+
+        let mut c: [[[Vec3; 2]; 2]; 2] = [[[Vec3::default(); 2]; 2]; 2];
+        for (di, plane) in c.iter_mut().enumerate() {
+            for (dj, row) in plane.iter_mut().enumerate() {
+                for (dk, corner) in row.iter_mut().enumerate() {
+                    *corner = self.randvec[self.perm_x[((i + di as i64) & 255) as usize]
+                        ^ self.perm_y[((j + dj as i64) & 255) as usize]
+                        ^ self.perm_z[((k + dk as i64) & 255) as usize]];
+                }
+            }
+        }
+
+        Self::perlin_interp(&c, u, v, w)
+    }
+
+    // Sums `depth` octaves of noise, each at twice the frequency and half the weight of
+    // the last.
+    pub fn turb(&self, p: &Point3, depth: u32) -> f64 {
+        let mut accum: f64 = 0_f64;
+        let mut temp_p: Point3 = *p;
+        let mut weight: f64 = 1_f64;
+
+        for _ in 0..depth {
+            accum += weight * self.noise(&temp_p);
+            weight *= 0.5;
+            temp_p *= 2_f64;
+        }
+
+        accum.abs()
+    }
+
+    fn generate_perm(rng: &mut Sampler) -> [usize; POINT_COUNT] {
+        let mut p: [usize; POINT_COUNT] = std::array::from_fn(|i| i);
+
+        // Fisher-Yates shuffle.
+        for i in (1..POINT_COUNT).rev() {
+            let target: usize = rng.random_int(0, i as i32) as usize;
+            p.swap(i, target);
+        }
+
+        p
+    }
+
+    // Trilinear interpolation of the gradient contributions, with Hermite smoothing of the
+    // fractional coordinates.
+    fn perlin_interp(c: &[[[Vec3; 2]; 2]; 2], u: f64, v: f64, w: f64) -> f64 {
+        let uu: f64 = u * u * (3_f64 - 2_f64 * u);
+        let vv: f64 = v * v * (3_f64 - 2_f64 * v);
+        let ww: f64 = w * w * (3_f64 - 2_f64 * w);
+        let mut accum: f64 = 0_f64;
+
+        for (i, plane) in c.iter().enumerate() {
+            for (j, row) in plane.iter().enumerate() {
+                for (k, corner) in row.iter().enumerate() {
+                    let (fi, fj, fk) = (i as f64, j as f64, k as f64);
+                    let weight_v: Vec3 = Vec3::new(u - fi, v - fj, w - fk);
+                    accum += (fi * uu + (1_f64 - fi) * (1_f64 - uu))
+                        * (fj * vv + (1_f64 - fj) * (1_f64 - vv))
+                        * (fk * ww + (1_f64 - fk) * (1_f64 - ww))
+                        * Vec3::dot(corner, &weight_v);
+                }
+            }
+        }
+
+        accum
+    }
+}
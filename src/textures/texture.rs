@@ -0,0 +1,7 @@
+use crate::geometry::vec3::{Color, Point3};
+
+// A color varying over a surface, looked up by the surface coordinates (u, v) of a hit and
+// by its position `p`.
+pub trait Texture: Send + Sync {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color;
+}
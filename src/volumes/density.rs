@@ -0,0 +1,12 @@
+use crate::geometry::vec3::Point3;
+
+// A scalar field over the unit cube [0,1]^3, such as the density of a cloud or the
+// temperature of a fire. Volumes stretch the cube over their bounds. Fields are zero
+// outside the cube and never negative.
+pub trait DensityField: Send + Sync {
+    fn density(&self, p: &Point3) -> f64;
+
+    // An upper bound of `density` over the whole cube. Tracking samples collisions against
+    // it, so a tight bound makes rendering faster, and a loose one only slower.
+    fn max_density(&self) -> f64;
+}
@@ -0,0 +1,41 @@
+use crate::geometry::vec3::{Point3, Vec3};
+
+use crate::sampling::sampler::Sampler;
+
+use crate::textures::perlin::Perlin;
+
+use super::density::DensityField;
+
+// Number of octaves of turbulence shaping the cloud.
+const TURBULENCE_DEPTH: u32 = 5;
+
+// A procedural cloud: a dense core at the center of the cube, thinning out into turbulent
+// wisps, and empty beyond the sphere inscribed in the cube. `scale` sets the frequency of
+// the turbulence across the cube.
+pub struct NoiseDensity {
+    noise: Perlin,
+    scale: f64,
+}
+
+impl NoiseDensity {
+    pub fn new(scale: f64, rng: &mut Sampler) -> Self {
+        Self { noise: Perlin::new(rng), scale }
+    }
+}
+
+impl DensityField for NoiseDensity {
+    fn density(&self, p: &Point3) -> f64 {
+        let radius: f64 = (*p - Vec3::new(0.5, 0.5, 0.5)).length();
+        if radius >= 0.5 {
+            return 0_f64;
+        }
+
+        let falloff: f64 = 1_f64 - 2_f64 * radius;
+        let turbulence: f64 = self.noise.turb(&(self.scale * *p), TURBULENCE_DEPTH);
+        (2_f64 * falloff - 1_f64 + turbulence).clamp(0_f64, 1_f64)
+    }
+
+    fn max_density(&self) -> f64 {
+        1_f64
+    }
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::geometry::vec3::Point3;
+
+use super::density::DensityField;
+
+// Sample types of a raw voxel file.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RawFormat {
+    // Bytes, mapped from [0,255] to [0,1].
+    U8,
+    // Little-endian 32-bit floats.
+    F32,
+}
+
+// A dense grid of density samples, stored with x varying fastest, then y, then z. Samples
+// sit at the centers of the voxels dividing the unit cube, and are blended trilinearly in
+// between.
+pub struct VoxelGrid {
+    resolution: [usize; 3],
+    values: Vec<f32>,
+    max: f64,
+}
+
+impl VoxelGrid {
+    // Negative samples are clamped to zero.
+    pub fn new(resolution: [usize; 3], values: Vec<f32>) -> Self {
+        let count: usize = resolution.iter().product();
+        assert!(count > 0, "voxel grid resolution must not be zero");
+        assert_eq!(values.len(), count, "voxel grid sample count does not match its resolution");
+
+        let values: Vec<f32> = values.into_iter().map(|value| value.max(0_f32)).collect();
+        let max: f64 = values.iter().fold(0_f32, |max, &value| max.max(value)) as f64;
+        Self { resolution, values, max }
+    }
+
+    // Reads a headerless file of samples; its size must match the resolution exactly.
+    pub fn load_raw(path: &Path, resolution: [usize; 3], format: RawFormat) -> io::Result<Self> {
+        Self::from_raw_bytes(&fs::read(path)?, resolution, format)
+    }
+
+    pub fn from_raw_bytes(bytes: &[u8], resolution: [usize; 3], format: RawFormat) -> io::Result<Self> {
+        let count: usize = resolution.iter().product();
+        let sample_size: usize = match format {
+            RawFormat::U8 => 1,
+            RawFormat::F32 => 4,
+        };
+        if count == 0 || bytes.len() != count * sample_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "expected {} bytes for {}x{}x{} samples, found {}",
+                    count * sample_size, resolution[0], resolution[1], resolution[2], bytes.len(),
+                ),
+            ));
+        }
+
+        let values: Vec<f32> = match format {
+            RawFormat::U8 => bytes.iter().map(|&byte| byte as f32 / 255_f32).collect(),
+            RawFormat::F32 => bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect(),
+        };
+        if values.iter().any(|value| !value.is_finite()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "samples must be finite"));
+        }
+        Ok(Self::new(resolution, values))
+    }
+
+    pub fn resolution(&self) -> [usize; 3] {
+        self.resolution
+    }
+
+    pub fn value(&self, i: usize, j: usize, k: usize) -> f64 {
+        let [nx, ny, _] = self.resolution;
+        self.values[(k * ny + j) * nx + i] as f64
+    }
+}
+
+impl DensityField for VoxelGrid {
+    fn density(&self, p: &Point3) -> f64 {
+        if (0..3).any(|axis| !(0_f64..=1_f64).contains(&p[axis])) {
+            return 0_f64;
+        }
+
+        // The two samples bracketing `p` along each axis, and the weight of the upper one.
+        // Beyond the outermost voxel centers the nearest sample is held.
+        let bracket = |axis: usize| -> (usize, usize, f64) {
+            let n: usize = self.resolution[axis];
+            let x: f64 = (p[axis] * n as f64 - 0.5).clamp(0_f64, (n - 1) as f64);
+            let lower: usize = x.floor() as usize;
+            (lower, (lower + 1).min(n - 1), x - lower as f64)
+        };
+        let (i0, i1, tx) = bracket(0);
+        let (j0, j1, ty) = bracket(1);
+        let (k0, k1, tz) = bracket(2);
+
+        let lerp = |a: f64, b: f64, t: f64| a + t * (b - a);
+        let plane = |k: usize| {
+            lerp(
+                lerp(self.value(i0, j0, k), self.value(i1, j0, k), tx),
+                lerp(self.value(i0, j1, k), self.value(i1, j1, k), tx),
+                ty,
+            )
+        };
+        lerp(plane(k0), plane(k1), tz)
+    }
+
+    fn max_density(&self) -> f64 {
+        self.max
+    }
+}